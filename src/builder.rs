@@ -0,0 +1,93 @@
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "history")]
+use crate::History;
+use crate::{Bus, BusInner, HookFn};
+
+/// Configures a `Bus` before it's created
+///
+/// `Bus::new()` creates a bus with every option left at its default.
+///
+/// ```
+/// use double_decker::BusBuilder;
+///
+/// let bus = BusBuilder::<u32>::new()
+///     .capacity_hint(4)
+///     .global_limit(1024)
+///     .pre_broadcast_hook(Box::new(|event| println!("Broadcasting {}", event)))
+///     .build();
+/// ```
+#[must_use]
+pub struct BusBuilder<T: Clone> {
+    inner: BusInner<T>,
+}
+
+impl<T: Clone> BusBuilder<T> {
+    pub fn new() -> Self {
+        BusBuilder {
+            inner: BusInner::default(),
+        }
+    }
+
+    /// Makes space for `n` `Receiver`s before reallocating
+    ///
+    /// See `Bus::with_capacity_hint()`.
+    pub fn capacity_hint(mut self, n: usize) -> Self {
+        self.inner.core.senders.reserve(n);
+        self
+    }
+
+    /// Limits the total number of events buffered across all `Receiver`s
+    ///
+    /// See `Bus::with_global_limit()`.
+    pub fn global_limit(mut self, max_total: usize) -> Self {
+        self.inner.global_limit = Some(max_total);
+        self
+    }
+
+    /// Makes every `Receiver` observe broadcasts in the same order
+    ///
+    /// See `Bus::with_total_order()`.
+    pub fn total_order(mut self) -> Self {
+        self.inner.total_order = true;
+        self
+    }
+
+    /// Retains the last `n` broadcast events
+    ///
+    /// See `Bus::with_history()`.
+    #[cfg(feature = "history")]
+    pub fn history(mut self, n: usize) -> Self {
+        self.inner.history = Some(History::new(n));
+        self
+    }
+
+    /// Sets a hook that is called with a reference to each event before it's broadcast
+    ///
+    /// See `Bus::set_pre_broadcast_hook()`.
+    pub fn pre_broadcast_hook(mut self, hook: HookFn<T>) -> Self {
+        self.inner.pre_broadcast = Some(hook);
+        self
+    }
+
+    /// Adds a tap that is called with a reference to each event on the broadcasting thread
+    ///
+    /// See `Bus::add_tap()`.
+    pub fn tap(mut self, tap: HookFn<T>) -> Self {
+        self.inner.taps.push(tap);
+        self
+    }
+
+    /// Creates the `Bus`
+    pub fn build(self) -> Bus<T> {
+        Bus {
+            inner: Arc::new(RwLock::new(self.inner)),
+        }
+    }
+}
+
+impl<T: Clone> Default for BusBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
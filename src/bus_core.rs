@@ -0,0 +1,224 @@
+use alloc::vec::Vec;
+
+/// Error returned by `ChannelSender::try_send()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelError<T> {
+    /// The channel is full so the event was not sent
+    Full(T),
+    /// The receiving half of the channel has been dropped
+    Disconnected(T),
+}
+
+/// The sending half of a channel used by a `BusCore`
+pub trait ChannelSender<T> {
+    fn try_send(&self, event: T) -> Result<(), ChannelError<T>>;
+}
+
+/// Creates the channels used by a `BusCore`
+pub trait ChannelFactory<T> {
+    type Sender: ChannelSender<T>;
+    type Receiver;
+
+    fn channel(&mut self) -> (Self::Sender, Self::Receiver);
+}
+
+pub(crate) struct Subscriber<S> {
+    pub(crate) id: usize,
+    pub(crate) priority: i32,
+    pub(crate) sender: S,
+}
+
+/// The bus bookkeeping without any locking or threads
+///
+/// This only depends on `alloc` so it can be used without the `std` feature by supplying a
+/// `ChannelFactory` for your platform. `Bus` is built on a `BusCore` of crossbeam channels and
+/// adds the locking needed to share it between threads.
+pub struct BusCore<T: Clone, C: ChannelFactory<T>> {
+    factory: C,
+    // Sorted by descending priority. Ids are handed out in increasing order and new senders are
+    // inserted after any others of the same priority, so ties are sorted by id.
+    pub(crate) senders: Vec<Subscriber<C::Sender>>,
+    pub(crate) next_id: usize,
+    // Set once `next_id` has wrapped around, after which ids may still be in use
+    wrapped: bool,
+}
+
+impl<T: Clone, C: ChannelFactory<T>> BusCore<T, C> {
+    /// Creates a new `BusCore` that creates its channels with `factory`
+    pub fn new(factory: C) -> Self {
+        BusCore::with_capacity(factory, 0)
+    }
+
+    pub(crate) fn with_capacity(factory: C, capacity: usize) -> Self {
+        BusCore {
+            factory,
+            senders: Vec::with_capacity(capacity),
+            next_id: 0,
+            wrapped: false,
+        }
+    }
+
+    /// Adds a new receiver to the bus
+    pub fn add_rx(&mut self) -> C::Receiver {
+        self.add_rx_with_id().0
+    }
+
+    pub(crate) fn add_rx_with_id(&mut self) -> (C::Receiver, usize) {
+        let (sender, receiver) = self.factory.channel();
+        let id = self.insert_sender(sender, 0);
+        (receiver, id)
+    }
+
+    /// Broadcast to all receivers in the order they were added
+    ///
+    /// Receivers that have been dropped are removed. Returns the number of receivers the event
+    /// was delivered to.
+    pub fn broadcast(&mut self, event: T) -> usize {
+        let (delivered, disconnected) = send_to(
+            self.senders.iter(),
+            clone_until_last(event),
+            C::Sender::try_send,
+        );
+
+        if !disconnected.is_empty() {
+            self.remove_senders(&disconnected);
+        }
+
+        delivered
+    }
+
+    /// Returns the number of receivers currently subscribed
+    pub fn subscriber_count(&self) -> usize {
+        self.senders.len()
+    }
+
+    /// Returns the id given to `sender`
+    pub(crate) fn insert_sender(&mut self, sender: C::Sender, priority: i32) -> usize {
+        let id = self.take_next_id();
+        let index = self
+            .senders
+            .iter()
+            .position(|subscriber| subscriber.priority < priority)
+            .unwrap_or(self.senders.len());
+        self.senders.insert(
+            index,
+            Subscriber {
+                id,
+                priority,
+                sender,
+            },
+        );
+        id
+    }
+
+    fn take_next_id(&mut self) -> usize {
+        loop {
+            let id = self.next_id;
+            let (next_id, overflowed) = self.next_id.overflowing_add(1);
+            self.next_id = next_id;
+            self.wrapped |= overflowed;
+
+            // Ids are unique until wrapping around, after which skip ids that are still in use
+            if !self.wrapped || !self.senders.iter().any(|subscriber| subscriber.id == id) {
+                return id;
+            }
+        }
+    }
+
+    /// Returns the ids that were removed
+    pub(crate) fn remove_senders(&mut self, ids: &[usize]) -> Vec<usize> {
+        let mut removed = Vec::with_capacity(0);
+
+        self.senders.retain(|subscriber| {
+            let remove = ids.contains(&subscriber.id);
+            if remove {
+                removed.push(subscriber.id);
+            }
+            !remove
+        });
+
+        removed
+    }
+}
+
+/// Sends the events returned by `next_event` to `senders` using `send`. `next_event` is passed
+/// `true` when the event is for the last sender.
+///
+/// Returns the number of senders the event was delivered to and the ids of any senders that
+/// were disconnected
+pub(crate) fn send_to<'a, T, S, I, F, G>(
+    senders: I,
+    mut next_event: F,
+    send: G,
+) -> (usize, Vec<usize>)
+where
+    S: 'a,
+    I: Iterator<Item = &'a Subscriber<S>>,
+    F: FnMut(bool) -> T,
+    G: Fn(&S, T) -> Result<(), ChannelError<T>>,
+{
+    let mut delivered = 0;
+    let mut disconnected = Vec::with_capacity(0);
+    let mut senders = senders.peekable();
+
+    while let Some(subscriber) = senders.next() {
+        let event = next_event(senders.peek().is_none());
+
+        match send(&subscriber.sender, event) {
+            Ok(()) => delivered += 1,
+            // Bounded receiver is full so it misses out on this event
+            Err(ChannelError::Full(_)) => {}
+            Err(ChannelError::Disconnected(_)) => disconnected.push(subscriber.id),
+        }
+    }
+
+    (delivered, disconnected)
+}
+
+/// The last sender gets the original event so we only clone for the others
+///
+/// Every sender still gets its own copy, so if the last sender is disconnected only the copy
+/// nobody could receive is lost. Cloning for every sender would deliver to exactly the same
+/// receivers.
+pub(crate) fn clone_until_last<T: Clone>(event: T) -> impl FnMut(bool) -> T {
+    let mut event = Some(event);
+
+    move |last| {
+        if last { event.take() } else { event.clone() }
+            .expect("Event is only taken by the last sender")
+    }
+}
+
+/// Creates unbounded crossbeam channels for a `BusCore`
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CrossbeamChannels;
+
+#[cfg(feature = "std")]
+impl<T> ChannelFactory<T> for CrossbeamChannels {
+    type Sender = crossbeam::channel::Sender<T>;
+    type Receiver = crossbeam::channel::Receiver<T>;
+
+    fn channel(&mut self) -> (Self::Sender, Self::Receiver) {
+        crossbeam::channel::unbounded()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> ChannelSender<T> for crossbeam::channel::Sender<T> {
+    fn try_send(&self, event: T) -> Result<(), ChannelError<T>> {
+        crossbeam::channel::Sender::try_send(self, event).map_err(ChannelError::from)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> From<crossbeam::channel::TrySendError<T>> for ChannelError<T> {
+    fn from(e: crossbeam::channel::TrySendError<T>) -> Self {
+        use crossbeam::channel::TrySendError;
+
+        match e {
+            TrySendError::Full(event) => ChannelError::Full(event),
+            TrySendError::Disconnected(event) => ChannelError::Disconnected(event),
+        }
+    }
+}
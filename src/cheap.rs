@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use crate::Bus;
+
+/// Marker for types whose `Clone` only bumps a reference count
+///
+/// Broadcasting clones the event for every `Receiver` but the last, so this is a promise that
+/// doing so is cheap. It's not implemented for anything by default apart from `Arc`. Types from
+/// other crates such as `bytes::Bytes` can be marked by wrapping them:
+///
+/// ```
+/// use double_decker::{Bus, CheapClone};
+/// use std::sync::Arc;
+///
+/// // Stands in for `bytes::Bytes` whose clones share the same buffer
+/// #[derive(Clone)]
+/// struct Payload(Arc<[u8]>);
+///
+/// impl CheapClone for Payload {}
+///
+/// let bus = Bus::new();
+/// let rx = bus.add_rx();
+///
+/// bus.broadcast_cheap(Payload(Arc::from(&b"Hello"[..])));
+/// assert_eq!(&*rx.recv().unwrap().0, b"Hello");
+/// ```
+pub trait CheapClone: Clone {}
+
+impl<T: ?Sized> CheapClone for Arc<T> {}
+
+impl<T: CheapClone> Bus<T> {
+    /// Broadcast to all `Receiver`s
+    ///
+    /// Behaves exactly like `broadcast()` but only accepts events that are cheap to clone, which
+    /// documents that no deep copies are made.
+    pub fn broadcast_cheap(&self, event: T) -> usize {
+        self.broadcast(event)
+    }
+}
+
+impl<T: Copy> Bus<T> {
+    /// Broadcast to all `Receiver`s by copying the event
+    ///
+    /// Behaves like `broadcast()` but each `Receiver` is sent a bitwise copy so `Clone` is never
+    /// called and nothing is allocated per `Receiver`.
+    pub fn broadcast_copy(&self, event: T) -> usize {
+        self.broadcast_with(|inner| {
+            inner.record(&event);
+            inner.broadcast_fn(|| event)
+        })
+    }
+}
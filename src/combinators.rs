@@ -0,0 +1,87 @@
+use crate::{Bus, SubscribeToReader, Subscription};
+
+impl<T: Clone + Send + 'static> Bus<T> {
+    /// Creates a new `Bus<U>` that broadcasts every event from this bus transformed by `f`
+    ///
+    /// A thread subscribes to this bus and broadcasts the transformed events onto the returned
+    /// bus. The thread exits once every handle to this bus has been dropped.
+    pub fn map<U, F>(&self, f: F) -> Bus<U>
+    where
+        U: Clone + Send + 'static,
+        F: Fn(T) -> U + Send + 'static,
+    {
+        let derived = Bus::new();
+        let sink = derived.clone();
+
+        self.subscribe_detached(Box::new(move |event| {
+            sink.broadcast(f(event));
+        }));
+
+        derived
+    }
+
+    /// Creates a new `Bus<U>` that broadcasts the events from this bus for which `f` returns
+    /// `Some`
+    ///
+    /// Like `map()`, the thread exits once every handle to this bus has been dropped.
+    pub fn filter_map<U, F>(&self, f: F) -> Bus<U>
+    where
+        U: Clone + Send + 'static,
+        F: Fn(T) -> Option<U> + Send + 'static,
+    {
+        let derived = Bus::new();
+        let sink = derived.clone();
+
+        self.subscribe_detached(Box::new(move |event| {
+            if let Some(event) = f(event) {
+                sink.broadcast(event);
+            }
+        }));
+
+        derived
+    }
+
+    /// Like `map` but intended as the start of a fan-in
+    ///
+    /// Other sources of a different type can then be merged into the returned bus with
+    /// `feed_from()`, for example to combine a `Bus<Clicks>` and a `Bus<Keys>` into a single
+    /// `Bus<Input>`.
+    pub fn merge_map<C, F>(&self, f: F) -> Bus<C>
+    where
+        C: Clone + Send + 'static,
+        F: Fn(T) -> C + Send + 'static,
+    {
+        self.map(f)
+    }
+
+    /// Broadcasts every event from `src` on this bus transformed by `g`
+    ///
+    /// Events are forwarded from a subscription thread until the returned `Subscription` is
+    /// dropped. Events from different sources are interleaved in the order they are forwarded.
+    #[must_use]
+    pub fn feed_from<B, G>(&self, src: &Bus<B>, g: G) -> Subscription
+    where
+        B: Clone + Send + 'static,
+        G: Fn(B) -> T + Send + 'static,
+    {
+        let sink = self.clone();
+        src.subscribe_on_thread(Box::new(move |event| {
+            sink.broadcast(g(event));
+        }))
+    }
+
+    /// Forwards every event broadcast on this bus to `other`
+    ///
+    /// Events are re-broadcast from a subscription thread until the returned `Subscription` is
+    /// dropped. Panics if `other` is this bus as every event would be forwarded back to itself
+    /// forever. Larger cycles such as connecting two buses to each other are not detected.
+    #[must_use]
+    pub fn connect_to(&self, other: &Bus<T>) -> Subscription {
+        assert!(!self.same_bus(other), "Can't connect a bus to itself");
+
+        let sink = other.clone();
+        self.subscribe_on_thread(Box::new(move |event| {
+            sink.broadcast(event);
+        }))
+    }
+}
@@ -0,0 +1,42 @@
+use std::sync::{Arc, Mutex};
+
+use crate::Subscription;
+
+/// A `Subscription` that folds every event into some state
+///
+/// Returned by `SubscribeToReader::subscribe_folding()`. The final state can be recovered with
+/// `into_state()`.
+pub struct FoldingSubscription<S> {
+    subscription: Subscription,
+    state: Arc<Mutex<S>>,
+}
+
+impl<S> FoldingSubscription<S> {
+    pub(crate) fn new(subscription: Subscription, state: Arc<Mutex<S>>) -> Self {
+        FoldingSubscription {
+            subscription,
+            state,
+        }
+    }
+
+    /// Terminates the subscription thread after folding any buffered events
+    ///
+    /// See `Subscription::dispose_draining()`.
+    pub fn dispose_draining(&self) {
+        self.subscription.dispose_draining();
+    }
+
+    /// Terminates the subscription thread and returns the final state
+    ///
+    /// This waits for the thread to exit. Events still buffered are discarded unless
+    /// `dispose_draining()` was called first. Panics if the callback panicked.
+    pub fn into_state(self) -> S {
+        self.subscription.join().expect("Folding callback panicked");
+
+        // The thread has exited so it no longer shares the state
+        match Arc::try_unwrap(self.state) {
+            Ok(state) => state.into_inner().expect("Lock was poisoned"),
+            Err(_) => unreachable!("Subscription thread has exited"),
+        }
+    }
+}
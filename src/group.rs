@@ -0,0 +1,44 @@
+use crate::Subscription;
+
+/// Holds several `Subscription`s so they can be terminated together
+///
+/// Every `Subscription` in the group is terminated when the group is dropped or `dispose()` is
+/// called, even if they have been cloned.
+#[derive(Default)]
+pub struct SubscriptionGroup {
+    subscriptions: Vec<Subscription>,
+}
+
+impl SubscriptionGroup {
+    /// Creates an empty `SubscriptionGroup`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a `Subscription` to the group
+    pub fn push(&mut self, subscription: Subscription) {
+        self.subscriptions.push(subscription);
+    }
+
+    /// Returns the number of `Subscription`s in the group
+    pub fn len(&self) -> usize {
+        self.subscriptions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subscriptions.is_empty()
+    }
+
+    /// Terminates every `Subscription` in the group
+    pub fn dispose(&self) {
+        for subscription in &self.subscriptions {
+            subscription.dispose();
+        }
+    }
+}
+
+impl Drop for SubscriptionGroup {
+    fn drop(&mut self) {
+        self.dispose();
+    }
+}
@@ -0,0 +1,117 @@
+use std::{hash::Hash, thread::JoinHandle, time::Duration};
+
+use crossbeam::channel::Receiver;
+
+use crate::{BoxedFn, Bus, SubscribeToReader, Subscription};
+
+/// A handle to a `Bus` that can only broadcast
+///
+/// Obtained from `Bus::sender()`. Cloning a `BusSender` is as cheap as cloning the `Bus` it came
+/// from and every clone broadcasts to the same subscribers.
+#[derive(Clone)]
+pub struct BusSender<T: Clone> {
+    bus: Bus<T>,
+}
+
+impl<T: Clone> BusSender<T> {
+    /// Broadcast to all `Receiver`s of the bus
+    ///
+    /// Returns the number of `Receiver`s the event was delivered to.
+    pub fn broadcast(&self, event: T) -> usize {
+        self.bus.broadcast(event)
+    }
+}
+
+/// A handle to a `Bus` that can only add `Receiver`s and subscribe
+///
+/// Obtained from `Bus::receiver()`.
+#[derive(Clone)]
+pub struct BusReceiver<T: Clone> {
+    bus: Bus<T>,
+}
+
+impl<T: Clone> BusReceiver<T> {
+    /// Adds a new `Receiver<T>` to the bus
+    pub fn add_rx(&self) -> Receiver<T> {
+        self.bus.add_rx()
+    }
+}
+
+impl<T: Clone + Send + 'static> SubscribeToReader<T> for BusReceiver<T> {
+    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription {
+        self.bus.subscribe_on_thread(callback)
+    }
+
+    fn subscribe(&self, callback: BoxedFn<T>) {
+        self.bus.subscribe(callback)
+    }
+
+    fn subscribe_until(&self, callback: BoxedFn<T>, should_stop: Box<dyn Fn() -> bool + Send>) {
+        self.bus.subscribe_until(callback, should_stop)
+    }
+
+    fn subscribe_idle_timeout(&self, idle: Duration, callback: BoxedFn<T>) {
+        self.bus.subscribe_idle_timeout(idle, callback)
+    }
+
+    fn subscribe_detached(&self, callback: BoxedFn<T>) -> JoinHandle<()> {
+        self.bus.subscribe_detached(callback)
+    }
+
+    fn subscribe_mapped<U: 'static>(
+        &self,
+        map: Box<dyn Fn(T) -> U + Send>,
+        callback: BoxedFn<U>,
+    ) -> Subscription {
+        self.bus.subscribe_mapped(map, callback)
+    }
+
+    fn subscribe_once(&self, callback: Box<dyn FnOnce(T) + Send>) -> Subscription {
+        self.bus.subscribe_once(callback)
+    }
+
+    fn subscribe_while(&self, callback: Box<dyn FnMut(T) -> bool + Send>) -> Subscription {
+        self.bus.subscribe_while(callback)
+    }
+
+    fn subscribe_batched(
+        &self,
+        max_batch: usize,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        self.bus.subscribe_batched(max_batch, callback)
+    }
+
+    fn subscribe_windowed(
+        &self,
+        window: Duration,
+        skip_empty: bool,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        self.bus.subscribe_windowed(window, skip_empty, callback)
+    }
+
+    fn subscribe_latest(&self, callback: BoxedFn<T>) -> Subscription {
+        self.bus.subscribe_latest(callback)
+    }
+
+    fn subscribe_coalesced<K, F>(&self, key_fn: F, callback: BoxedFn<T>) -> Subscription
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K + Send + 'static,
+    {
+        self.bus.subscribe_coalesced(key_fn, callback)
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Returns a handle that can broadcast to this bus but can't add `Receiver`s
+    pub fn sender(&self) -> BusSender<T> {
+        BusSender { bus: self.clone() }
+    }
+
+    /// Returns a handle that can add `Receiver`s to this bus but can't broadcast
+    pub fn receiver(&self) -> BusReceiver<T> {
+        BusReceiver { bus: self.clone() }
+    }
+}
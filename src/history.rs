@@ -0,0 +1,98 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crossbeam::channel::{unbounded, Receiver};
+
+use crate::{Bus, BusInner, RecvExt};
+
+/// Bounded buffer of the most recently broadcast events
+pub(crate) struct History<T> {
+    capacity: usize,
+    events: Mutex<VecDeque<T>>,
+}
+
+impl<T: Clone> History<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        History {
+            capacity,
+            events: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    pub fn record(&self, event: &T) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut events = self.events.lock().expect("Lock was poisoned");
+        if events.len() == self.capacity {
+            events.pop_front();
+        }
+        events.push_back(event.clone());
+    }
+
+    pub fn events(&self) -> Vec<T> {
+        let events = self.events.lock().expect("Lock was poisoned");
+        events.iter().cloned().collect()
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Creates a new `double_decker::Bus` that retains the last `n` broadcast events
+    ///
+    /// Retained events can be fetched with `history()` and are replayed to `Receiver`s added with
+    /// `add_rx_replay()`. Events from `broadcast_fn()` aren't retained as each `Receiver` gets a
+    /// different event.
+    pub fn with_history(n: usize) -> Self {
+        let inner = BusInner {
+            history: Some(History::new(n)),
+            ..Default::default()
+        };
+
+        Bus {
+            inner: Arc::new(RwLock::new(inner)),
+        }
+    }
+
+    /// Returns the retained events, oldest first
+    ///
+    /// Always empty for buses not created with `with_history()`.
+    pub fn history(&self) -> Vec<T> {
+        self.inner
+            .read()
+            .expect("Lock was poisoned")
+            .history
+            .as_ref()
+            .map(History::events)
+            .unwrap_or_default()
+    }
+
+    /// Adds a new `Receiver<T>` that first receives the retained events and then live events
+    ///
+    /// No events are missed or duplicated between the replayed history and live events.
+    pub fn add_rx_replay(&self) -> Receiver<T> {
+        self.add_rx_lagged(0)
+    }
+
+    /// Like `add_rx_replay()` but the oldest `skip` retained events aren't replayed
+    ///
+    /// A new `Receiver` otherwise starts empty, so this only skips replayed events and never
+    /// live ones.
+    pub fn add_rx_lagged(&self, skip: usize) -> Receiver<T> {
+        let mut inner = self.inner.write().expect("Lock was poisoned");
+        let (sender, receiver) = unbounded::<T>();
+
+        // Holding the write lock stops broadcasts until the sender is registered
+        if let Some(history) = &inner.history {
+            for event in history.events() {
+                let _ = sender.send(event);
+            }
+        }
+        receiver.skip(skip);
+
+        inner.insert_sender(sender);
+        receiver
+    }
+}
@@ -19,15 +19,28 @@ The [`bus`](https://crates.io/crates/bus) crate is mature and completely lock-fr
 `T` must implement `Clone` so it can be passed to all consumers.
 
 When you call `add_rx()`, a `Sender`/`Receiver` pair are created and the `Sender` is
-stored in a `HashMap` behind a `RwLock`.
+stored in a `Vec` behind a `RwLock`.
 
 `broadcast()` uses shared read access of the `RwLock` and sends out events to each `Receiver` in the
-order they were added.
+order they were added. `Receiver`s added with `add_rx_priority()` are sent events in order of
+priority instead.
+
+Because `broadcast()` only needs shared access, concurrent broadcasts from different threads can
+interleave. Each `Receiver` sees the events from any one producer in the order they were broadcast,
+but two `Receiver`s may see events from different producers in a different order. If every
+`Receiver` must observe the same global order, use `broadcast_ordered()` which takes exclusive
+access of the `RwLock` for the whole broadcast. Buses created with `with_total_order()` do this for
+every broadcast.
 
 Lock contention can only occur when the number of subscribers changes as this requires write access to
 the `RwLock`. This occurs when you call `add_rx()` or when you call `broadcast()` and one or more
 `Sender` returns `SendError` because it's become disconnected.
 
+A crossbeam `Sender` can only discover that its `Receiver` has been dropped by trying to send it
+an event, so the bus can't report whether a `Receiver` is still connected or prune dropped
+`Receiver`s without broadcasting. `broadcast_lossy()` reports the ids of any `Receiver`s found to
+be disconnected.
+
 # Examples plagiarised from `bus` crate
 
 Single-send, multi-consumer example
@@ -95,74 +108,440 @@ let _subscription = bus.subscribe_on_thread(Box::new(move |_event| {
 
 bus.broadcast(5);
 ```
+
+# Features
+
+- `async` - Adds `Bus::subscribe_stream()` which returns a `futures::Stream` of events,
+  `Bus::add_rx_async()` which returns a `Receiver` that can be awaited,
+  `Bus::subscribe_on_task()` which calls the callback from a tokio blocking task and implements
+  `futures::Sink` for `Bus`
+- `history` - Adds `Bus::with_history()` which retains recent events so they can be replayed to
+  new `Receiver`s
+- `rayon` - Adds `Bus::broadcast_parallel()` which sends to `Receiver`s in parallel
+- `testing` - Adds `TestBus` which keeps its own `Receiver`s so tests can check what was
+  broadcast without threads
+- `std` (default) - Everything apart from `BusCore`, which only needs `alloc` so it can be used
+  in `no_std` environments with your own `ChannelFactory`
 */
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
 use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
-    thread,
+    collections::{hash_map::Entry, HashMap},
+    fmt,
+    hash::Hash,
+    iter, mem,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
 };
 
-use crossbeam::channel::{bounded, unbounded, Receiver, Sender, TryRecvError};
+#[cfg(feature = "std")]
+use crossbeam::channel::{
+    bounded, select, unbounded, Receiver, RecvTimeoutError, SendTimeoutError, Sender, TrySendError,
+};
 
+#[cfg(feature = "std")]
+mod builder;
+mod bus_core;
+#[cfg(feature = "std")]
+mod cheap;
+#[cfg(feature = "std")]
+mod combinators;
+#[cfg(feature = "std")]
+mod folding;
+#[cfg(feature = "std")]
+mod group;
+#[cfg(feature = "std")]
+mod handles;
+#[cfg(feature = "history")]
+mod history;
+#[cfg(feature = "std")]
+mod lifecycle;
+#[cfg(feature = "std")]
+mod limit;
+#[cfg(feature = "std")]
+mod lossy;
+#[cfg(feature = "std")]
+mod many;
+#[cfg(feature = "std")]
+mod notify;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod polling;
+#[cfg(feature = "std")]
+mod reconnecting;
+#[cfg(feature = "std")]
+mod recv;
+#[cfg(feature = "std")]
+mod replaceable;
+#[cfg(feature = "std")]
+mod seq;
+#[cfg(feature = "async")]
+mod sink;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "async")]
+mod task;
+#[cfg(feature = "testing")]
+mod testing;
+#[cfg(feature = "std")]
+mod topic;
+#[cfg(feature = "std")]
+mod worker;
+#[cfg(feature = "std")]
+pub use builder::BusBuilder;
+#[cfg(feature = "std")]
+use bus_core::clone_until_last;
+#[cfg(feature = "std")]
+pub use bus_core::CrossbeamChannels;
+pub use bus_core::{BusCore, ChannelError, ChannelFactory, ChannelSender};
+#[cfg(feature = "std")]
+pub use cheap::CheapClone;
+#[cfg(feature = "std")]
+pub use folding::FoldingSubscription;
+#[cfg(feature = "std")]
+pub use group::SubscriptionGroup;
+#[cfg(feature = "std")]
+pub use handles::{BusReceiver, BusSender};
+#[cfg(feature = "history")]
+use history::History;
+#[cfg(feature = "std")]
+pub use lifecycle::LifecycleEvent;
+#[cfg(feature = "std")]
+pub use lossy::BroadcastReport;
+#[cfg(feature = "std")]
+pub use many::subscribe_many;
+#[cfg(feature = "std")]
+pub use polling::PollingSubscription;
+#[cfg(feature = "std")]
+pub use reconnecting::ReconnectingReceiver;
+#[cfg(feature = "std")]
+pub use recv::RecvExt;
+#[cfg(feature = "std")]
+pub use replaceable::ReplaceableSubscription;
+#[cfg(feature = "std")]
+pub use stats::BusStats;
+#[cfg(feature = "std")]
+use stats::Stats;
+#[cfg(feature = "async")]
+pub use stream::{AsyncReceiver, EventStream};
+#[cfg(feature = "testing")]
+pub use testing::TestBus;
+#[cfg(feature = "std")]
+pub use topic::TopicBus;
+#[cfg(feature = "std")]
+use worker::Next;
+#[cfg(feature = "std")]
+pub use worker::WorkerMode;
+
+#[cfg(feature = "std")]
+type DisconnectFn = Arc<dyn Fn(usize) + Send + Sync>;
+#[cfg(feature = "std")]
+type HookFn<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+#[cfg(feature = "std")]
+type Subscriber<T> = bus_core::Subscriber<Sender<T>>;
+
+#[cfg(feature = "std")]
 struct BusInner<T: Clone> {
-    senders: HashMap<usize, Sender<T>>,
-    next_id: usize,
+    // Ids, senders and their priorities
+    core: BusCore<T, CrossbeamChannels>,
+    on_disconnect: Option<DisconnectFn>,
+    // Set by `Bus::with_total_order()` to serialise every broadcast
+    total_order: bool,
+    // Set by `Bus::with_global_limit()`
+    global_limit: Option<usize>,
+    // Set by `Bus::set_pre_broadcast_hook()`
+    pre_broadcast: Option<HookFn<T>>,
+    // Added by `Bus::add_tap()`
+    taps: Vec<HookFn<T>>,
+    // Set by `Bus::close()` to reject further broadcasts
+    closed: bool,
+    // Observers added by `Bus::lifecycle()`
+    lifecycle: Vec<Sender<LifecycleEvent>>,
+    // Last event passed to `Bus::broadcast_if_changed()`. Behind a `Mutex` so the bus doesn't
+    // require `T: Sync`.
+    last_event: Mutex<Option<T>>,
+    // Next sequence number handed out by `Bus::broadcast_seq()`
+    seq: AtomicU64,
+    stats: Stats,
+    #[cfg(feature = "history")]
+    history: Option<History<T>>,
 }
 
+#[cfg(feature = "std")]
 impl<T: Clone> BusInner<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        BusInner {
+            core: BusCore::with_capacity(CrossbeamChannels, capacity),
+            on_disconnect: None,
+            total_order: false,
+            global_limit: None,
+            pre_broadcast: None,
+            taps: Vec::new(),
+            closed: false,
+            lifecycle: Vec::new(),
+            last_event: Mutex::new(None),
+            seq: AtomicU64::new(0),
+            stats: Stats::default(),
+            #[cfg(feature = "history")]
+            history: None,
+        }
+    }
+
     pub fn add_rx(&mut self) -> Receiver<T> {
+        self.add_rx_with_id().0
+    }
+
+    pub fn add_rx_with_id(&mut self) -> (Receiver<T>, usize) {
+        let (receiver, id) = self.core.add_rx_with_id();
+        self.notify_lifecycle(LifecycleEvent::Added(id));
+        (receiver, id)
+    }
+
+    pub fn add_rx_with_capacity(&mut self, cap: usize) -> Receiver<T> {
+        let (sender, receiver) = bounded::<T>(cap);
+        self.insert_sender(sender);
+        receiver
+    }
+
+    pub fn add_rx_priority(&mut self, priority: i32) -> Receiver<T> {
         let (sender, receiver) = unbounded::<T>();
-        self.senders.insert(self.next_id, sender);
-        self.next_id += 1;
+        self.insert_sender_with_priority(sender, priority);
         receiver
     }
 
-    pub fn broadcast(&self, event: T) -> Vec<usize> {
+    pub fn broadcast(&self, event: T) -> (usize, Vec<usize>) {
+        self.record(&event);
+
+        // Nobody is listening so there's nothing to send
+        if self.core.senders.is_empty() {
+            self.stats.record_broadcast(0);
+            return (0, Vec::new());
+        }
+
+        // Skip the fan-out machinery in the common one-to-one case
+        if let (None, [subscriber]) = (self.global_limit, self.core.senders.as_slice()) {
+            let result = match subscriber.sender.try_send(event) {
+                Ok(()) => (1, Vec::new()),
+                Err(TrySendError::Full(_)) => (0, Vec::new()),
+                Err(TrySendError::Disconnected(_)) => (0, vec![subscriber.id]),
+            };
+            self.stats.record_broadcast(result.0);
+            return result;
+        }
+
+        self.broadcast_to(self.fan_out(), clone_until_last(event), Sender::try_send)
+    }
+
+    pub fn broadcast_blocking(&self, event: T) -> (usize, Vec<usize>) {
+        self.record(&event);
+        self.broadcast_to(self.fan_out(), clone_until_last(event), |sender, event| {
+            sender
+                .send(event)
+                .map_err(|e| TrySendError::Disconnected(e.into_inner()))
+        })
+    }
+
+    /// Returns the number of `Sender`s the event was delivered to, the ids of any `Sender`s that
+    /// timed out and the ids of any `Sender`s that were disconnected
+    pub fn broadcast_timeout(
+        &self,
+        event: T,
+        timeout: Duration,
+    ) -> (usize, Vec<usize>, Vec<usize>) {
+        self.record(&event);
+
+        let mut delivered = 0;
+        let mut timed_out = Vec::with_capacity(0);
         let mut disconnected = Vec::with_capacity(0);
+        let mut next_event = clone_until_last(event);
+        let mut senders = self.fan_out().peekable();
 
-        if let Some(((last_id, last_sender), the_rest)) = self.get_sorted_senders().split_last() {
-            for (id, sender) in the_rest.iter() {
-                if sender.send(event.clone()).is_err() {
-                    disconnected.push(**id);
-                }
+        while let Some(subscriber) = senders.next() {
+            let event = next_event(senders.peek().is_none());
+
+            match subscriber.sender.send_timeout(event, timeout) {
+                Ok(()) => delivered += 1,
+                Err(SendTimeoutError::Timeout(_)) => timed_out.push(subscriber.id),
+                Err(SendTimeoutError::Disconnected(_)) => disconnected.push(subscriber.id),
             }
+        }
 
-            if last_sender.send(event).is_err() {
-                disconnected.push(**last_id);
-            };
+        self.stats.record_broadcast(delivered);
+        (delivered, timed_out, disconnected)
+    }
+
+    pub fn broadcast_ref(&self, event: &T) -> (usize, Vec<usize>) {
+        self.record(event);
+        self.broadcast_to(self.fan_out(), |_| event.clone(), Sender::try_send)
+    }
+
+    pub fn broadcast_fn<F>(&self, mut factory: F) -> (usize, Vec<usize>)
+    where
+        F: FnMut() -> T,
+    {
+        self.broadcast_to(self.fan_out(), |_| factory(), Sender::try_send)
+    }
+
+    pub fn broadcast_all<I>(&self, events: I) -> (usize, Vec<usize>)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut delivered = 0;
+        let mut disconnected = Vec::with_capacity(0);
+
+        for event in events {
+            let (count, ids) = self.broadcast(event);
+            delivered += count;
+
+            // Disconnected senders fail for every remaining event so only record them once
+            for id in ids {
+                if !disconnected.contains(&id) {
+                    disconnected.push(id);
+                }
+            }
         }
 
-        disconnected
+        (delivered, disconnected)
+    }
+
+    pub fn broadcast_except(&self, event: T, except: usize) -> (usize, Vec<usize>) {
+        self.record(&event);
+        self.broadcast_to(
+            self.fan_out().filter(|subscriber| subscriber.id != except),
+            clone_until_last(event),
+            Sender::try_send,
+        )
     }
 
-    pub fn remove_senders(&mut self, ids: &[usize]) {
-        for id in ids {
-            self.senders.remove(&id);
+    /// Sends the events returned by `next_event` to `senders` using `send`. `next_event` is
+    /// passed `true` when the event is for the last sender.
+    ///
+    /// Returns the number of `Sender`s the event was delivered to and the ids of any `Sender`s
+    /// that were disconnected
+    fn broadcast_to<'a, I, F>(
+        &self,
+        senders: I,
+        next_event: F,
+        send: fn(&Sender<T>, T) -> Result<(), TrySendError<T>>,
+    ) -> (usize, Vec<usize>)
+    where
+        I: Iterator<Item = &'a Subscriber<T>>,
+        F: FnMut(bool) -> T,
+        T: 'a,
+    {
+        let (delivered, disconnected) = bus_core::send_to(senders, next_event, |sender, event| {
+            send(sender, event).map_err(ChannelError::from)
+        });
+
+        self.stats.record_broadcast(delivered);
+        (delivered, disconnected)
+    }
+
+    /// Called with every event before it's sent to any `Sender`s
+    fn record(&self, event: &T) {
+        if let Some(hook) = &self.pre_broadcast {
+            hook(event);
+        }
+        for tap in &self.taps {
+            tap(event);
+        }
+
+        #[cfg(feature = "history")]
+        if let Some(history) = &self.history {
+            history.record(event);
         }
     }
 
-    fn get_sorted_senders(&self) -> Vec<(&usize, &Sender<T>)> {
-        let mut senders = self.senders.iter().collect::<Vec<(&usize, &Sender<T>)>>();
-        senders.sort_by_key(|(id, _)| **id);
-        senders
+    fn insert_sender(&mut self, sender: Sender<T>) -> usize {
+        self.insert_sender_with_priority(sender, 0)
+    }
+
+    fn insert_sender_with_priority(&mut self, sender: Sender<T>, priority: i32) -> usize {
+        let id = self.core.insert_sender(sender, priority);
+        self.notify_lifecycle(LifecycleEvent::Added(id));
+        id
+    }
+
+    /// Removes disconnected senders and returns the ids that were removed
+    pub fn prune(&mut self, ids: &[usize]) -> Vec<usize> {
+        let removed = self.remove_senders(ids);
+        self.stats.record_pruned(removed.len());
+        removed
+    }
+
+    /// Returns the ids that were removed
+    pub fn remove_senders(&mut self, ids: &[usize]) -> Vec<usize> {
+        let removed = self.core.remove_senders(ids);
+        for id in &removed {
+            self.notify_lifecycle(LifecycleEvent::Removed(*id));
+        }
+        removed
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Clone> Default for BusInner<T> {
     fn default() -> Self {
-        BusInner {
-            senders: Default::default(),
-            next_id: 0,
+        BusInner::with_capacity(0)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Error returned by `Bus::try_broadcast()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BroadcastError {
+    /// The event wasn't delivered to any `Receiver`s
+    NoSubscribers,
+    /// The lock was poisoned by a thread that panicked while holding it
+    Poisoned,
+    /// The bus has been closed with `Bus::close()`
+    Closed,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BroadcastError::NoSubscribers => write!(f, "event was not delivered to any receivers"),
+            BroadcastError::Poisoned => write!(f, "lock was poisoned"),
+            BroadcastError::Closed => write!(f, "bus has been closed"),
         }
     }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for BroadcastError {}
+
+#[cfg(feature = "std")]
+/// Identifies a `Receiver` registered with a `Bus`
+///
+/// Returned by `Bus::add_rx_with_handle()` and used to explicitly remove the `Receiver` via
+/// `Bus::remove_rx()` or to exclude it from `Bus::broadcast_except()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RxHandle {
+    id: usize,
+}
+
+#[cfg(feature = "std")]
 #[derive(Clone)]
 pub struct Bus<T: Clone> {
     inner: Arc<RwLock<BusInner<T>>>,
 }
 
+#[cfg(feature = "std")]
 impl<T: Clone> Bus<T> {
     /// Creates a new `double_decker::Bus`
     pub fn new() -> Self {
@@ -171,147 +550,1454 @@ impl<T: Clone> Bus<T> {
         }
     }
 
+    /// Creates a new `double_decker::Bus` with space for `n` `Receiver`s before reallocating
+    pub fn with_capacity_hint(n: usize) -> Self {
+        Bus {
+            inner: Arc::new(RwLock::new(BusInner::with_capacity(n))),
+        }
+    }
+
+    /// Creates a new `double_decker::Bus` where every `Receiver` observes broadcasts in the same
+    /// order
+    ///
+    /// Every broadcast takes exclusive access to the bus as `broadcast_ordered()` does, so
+    /// concurrent broadcasts are serialised rather than sent in parallel. This lowers throughput
+    /// when there are many producers.
+    pub fn with_total_order() -> Self {
+        let inner = BusInner {
+            total_order: true,
+            ..Default::default()
+        };
+
+        Bus {
+            inner: Arc::new(RwLock::new(inner)),
+        }
+    }
+
     /// Adds a new `Receiver<T>`
     pub fn add_rx(&self) -> Receiver<T> {
         self.inner.write().expect("Lock was poisoned").add_rx()
     }
 
+    /// Adds a new `Receiver<T>` and returns it along with the bus
+    ///
+    /// This is a convenience over `let rx = bus.add_rx()` for the common case of a single
+    /// consumer.
+    pub fn split(self) -> (Bus<T>, Receiver<T>) {
+        let receiver = self.add_rx();
+        (self, receiver)
+    }
+
+    /// Adds a new `Receiver<T>` along with a handle that can be used to remove it
+    pub fn add_rx_with_handle(&self) -> (Receiver<T>, RxHandle) {
+        let (receiver, id) = self
+            .inner
+            .write()
+            .expect("Lock was poisoned")
+            .add_rx_with_id();
+        (receiver, RxHandle { id })
+    }
+
+    /// Removes the `Receiver` identified by `handle` from the bus
+    ///
+    /// The `Receiver` is disconnected immediately rather than waiting for it to be dropped and
+    /// reaped by the next `broadcast()`. Removing a `Receiver` that has already been removed is a
+    /// no-op.
+    pub fn remove_rx(&self, handle: RxHandle) {
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .remove_senders(&[handle.id]);
+    }
+
+    /// Removes every `Receiver` from the bus
+    ///
+    /// Each `Receiver` is disconnected once it has received any events already buffered. Ids are
+    /// only reused once they wrap around after `usize::MAX` `Receiver`s have been added, so until
+    /// then handles to `Receiver`s removed by this won't match new `Receiver`s.
+    pub fn clear(&self) {
+        let mut inner = self.inner.write().expect("Lock was poisoned");
+        let ids = inner
+            .core
+            .senders
+            .iter()
+            .map(|subscriber| subscriber.id)
+            .collect::<Vec<_>>();
+        inner.remove_senders(&ids);
+    }
+
+    /// Stops the bus accepting any more events
+    ///
+    /// Subsequent broadcasts deliver nothing and `try_broadcast()` returns
+    /// `BroadcastError::Closed`. Events already broadcast stay buffered in the `Receiver`s so
+    /// consumers can finish draining them. `Receiver`s are disconnected once every handle to the
+    /// bus has been dropped.
+    pub fn close(&self) {
+        self.inner.write().expect("Lock was poisoned").closed = true;
+    }
+
+    /// Returns `true` if `close()` has been called
+    pub fn is_closed(&self) -> bool {
+        self.inner.read().expect("Lock was poisoned").closed
+    }
+
+    /// Adds a new bounded `Receiver<T>` that can buffer at most `cap` events
+    ///
+    /// If the `Receiver` is full when `broadcast()` is called, the event is dropped for this
+    /// `Receiver` only. Unlike a disconnected `Receiver`, a full one is not removed from the bus
+    /// and will receive later events once it has room.
+    pub fn add_rx_with_capacity(&self, cap: usize) -> Receiver<T> {
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .add_rx_with_capacity(cap)
+    }
+
+    /// Adds a new `Receiver<T>` that is sent events before any `Receiver`s of lower priority
+    ///
+    /// `Receiver`s added by the other methods have a priority of zero. `Receiver`s with the same
+    /// priority are sent events in the order they were added.
+    pub fn add_rx_priority(&self, priority: i32) -> Receiver<T> {
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .add_rx_priority(priority)
+    }
+
     /// Broadcast to all `Receiver`s
-    pub fn broadcast(&self, event: T) {
-        let disconnected = {
-            self.inner
-                .read()
-                .expect("Lock was poisoned")
-                .broadcast(event)
+    ///
+    /// Returns the number of `Receiver`s the event was delivered to. Any disconnected `Sender`s
+    /// encountered are pruned and bounded `Receiver`s that are full miss out on the event. Neither
+    /// are included in the count.
+    ///
+    /// The event is cloned for every `Receiver` but the last. For reference counted types such as
+    /// `Arc` or `bytes::Bytes` this only copies a pointer, see `CheapClone`.
+    pub fn broadcast(&self, event: T) -> usize {
+        self.broadcast_with(|inner| inner.broadcast(event))
+    }
+
+    /// Broadcast a clone of `event` to all `Receiver`s
+    ///
+    /// Unlike `broadcast()`, this doesn't take ownership of the event so every `Receiver` gets a
+    /// clone. Returns the number of `Receiver`s the event was delivered to.
+    pub fn broadcast_ref(&self, event: &T) -> usize {
+        self.broadcast_with(|inner| inner.broadcast_ref(event))
+    }
+
+    /// Broadcast a new event from `factory` to each `Receiver`
+    ///
+    /// Rather than cloning a single event, `factory` is called once per `Receiver`. It's called
+    /// in the same order that `Receiver`s are sent events, which is the order they were added.
+    /// As there's no single event, the pre-broadcast hook, taps and history don't see events
+    /// broadcast this way. Returns the number of `Receiver`s an event was delivered to.
+    pub fn broadcast_fn<F>(&self, factory: F) -> usize
+    where
+        F: FnMut() -> T,
+    {
+        self.broadcast_with(|inner| inner.broadcast_fn(factory))
+    }
+
+    /// Broadcast each of `events` in order to all `Receiver`s
+    ///
+    /// This is more efficient than calling `broadcast()` in a loop since the lock is only taken
+    /// once for the whole batch and disconnected `Sender`s are pruned once at the end. Returns the
+    /// total number of deliveries across all events.
+    pub fn broadcast_all<I>(&self, events: I) -> usize
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.broadcast_with(|inner| inner.broadcast_all(events))
+    }
+
+    /// Broadcast to all `Receiver`s, blocking until every bounded `Receiver` has room
+    ///
+    /// Rather than dropping the event for bounded `Receiver`s that are full, this waits until
+    /// they have room. Unbounded `Receiver`s never block. Returns the number of `Receiver`s the
+    /// event was delivered to.
+    ///
+    /// The lock is held while blocking so `add_rx()`, `remove_rx()` and pruning all wait until
+    /// this returns, as may other broadcasts. This deadlocks if a full bounded `Receiver` is only
+    /// drained by a thread that is itself waiting on the bus, such as the thread calling this
+    /// method or one calling `add_rx()`.
+    pub fn broadcast_blocking(&self, event: T) -> usize {
+        self.broadcast_with(|inner| inner.broadcast_blocking(event))
+    }
+
+    /// Broadcast to all `Receiver`s, waiting up to `timeout` for each full bounded `Receiver`
+    ///
+    /// Returns the ids of the `Receiver`s that were still full after waiting. Unlike disconnected
+    /// `Receiver`s, these are not removed from the bus as they may just be slow.
+    ///
+    /// The timeout applies to each `Receiver` separately so this can block for `timeout`
+    /// multiplied by the number of full `Receiver`s. As with `broadcast_blocking()`, the lock is
+    /// held while waiting.
+    pub fn broadcast_timeout(&self, event: T, timeout: Duration) -> Vec<usize> {
+        let mut timed_out = Vec::with_capacity(0);
+
+        self.broadcast_with(|inner| {
+            let (delivered, slow, disconnected) = inner.broadcast_timeout(event, timeout);
+            timed_out = slow;
+            (delivered, disconnected)
+        });
+
+        timed_out
+    }
+
+    /// Broadcast a request and wait up to `timeout` for the first reply
+    ///
+    /// `make_event` is passed the `Sender` subscribers should reply on. Only the first reply is
+    /// returned and any later ones are dropped. Returns `RecvTimeoutError::Disconnected` without
+    /// waiting for the timeout if every copy of the request is dropped without a reply.
+    pub fn request_reply<R, F>(
+        &self,
+        make_event: F,
+        timeout: Duration,
+    ) -> Result<R, RecvTimeoutError>
+    where
+        F: FnOnce(Sender<R>) -> T,
+    {
+        let (reply_tx, reply_rx) = bounded(1);
+        self.broadcast(make_event(reply_tx));
+        reply_rx.recv_timeout(timeout)
+    }
+
+    /// Broadcast to all `Receiver`s except the one identified by `handle`
+    ///
+    /// This is useful when a participant both produces and consumes on the same bus and shouldn't
+    /// receive its own events. Returns the number of `Receiver`s the event was delivered to.
+    pub fn broadcast_except(&self, event: T, handle: RxHandle) -> usize {
+        self.broadcast_with(|inner| inner.broadcast_except(event, handle.id))
+    }
+
+    /// Broadcast to all `Receiver`s, returning an error if the event wasn't delivered
+    ///
+    /// Unlike `broadcast()`, this doesn't panic if the lock is poisoned and lets callers
+    /// distinguish an event that reached no `Receiver`s from one that reached some.
+    pub fn try_broadcast(&self, event: T) -> Result<usize, BroadcastError> {
+        match self.try_broadcast_with(|inner| inner.broadcast(event))? {
+            0 => Err(BroadcastError::NoSubscribers),
+            delivered => Ok(delivered),
+        }
+    }
+
+    fn broadcast_with<F>(&self, broadcast: F) -> usize
+    where
+        F: FnOnce(&BusInner<T>) -> (usize, Vec<usize>),
+    {
+        match self.try_broadcast_with(broadcast) {
+            Ok(delivered) => delivered,
+            Err(BroadcastError::Closed) => 0,
+            Err(_) => panic!("Lock was poisoned"),
+        }
+    }
+
+    fn try_broadcast_with<F>(&self, broadcast: F) -> Result<usize, BroadcastError>
+    where
+        F: FnOnce(&BusInner<T>) -> (usize, Vec<usize>),
+    {
+        let (delivered, disconnected) = {
+            let inner = self.inner.read().map_err(|_| BroadcastError::Poisoned)?;
+            if inner.closed {
+                return Err(BroadcastError::Closed);
+            }
+            if inner.total_order {
+                drop(inner);
+                return self.try_broadcast_exclusive_with(|inner| broadcast(inner));
+            }
+            broadcast(&inner)
         };
 
+        // Other threads may add or remove `Receiver`s before we get write access. That's fine as
+        // pruning removes by id and an id still registered is never handed out again, so only the
+        // disconnected senders go. The exception is if, after ids have wrapped around, another
+        // thread prunes one of them and it's handed to a new `Receiver` in between.
         if !disconnected.is_empty() {
-            self.inner
-                .write()
-                .expect("Lock was poisoned")
-                .remove_senders(&disconnected);
+            let (removed, on_disconnect) = {
+                let mut inner = self.inner.write().map_err(|_| BroadcastError::Poisoned)?;
+                (inner.prune(&disconnected), inner.on_disconnect.clone())
+            };
+
+            report_disconnected(on_disconnect, removed);
+        }
+
+        Ok(delivered)
+    }
+
+    /// Broadcast to all `Receiver`s while holding exclusive access to the bus
+    ///
+    /// Concurrent calls to `broadcast()` may interleave so different `Receiver`s can observe
+    /// events from different producers in a different order. Concurrent calls to this method
+    /// are serialised so every `Receiver` observes them in the same order. This comes at the
+    /// cost of blocking all other access to the bus while sending. Returns the number of
+    /// `Receiver`s the event was delivered to.
+    pub fn broadcast_ordered(&self, event: T) -> usize {
+        self.broadcast_exclusive_with(|inner| inner.broadcast(event))
+    }
+
+    /// Broadcast to all `Receiver`s only if `event` differs from the last event passed to this
+    /// method
+    ///
+    /// This suits buses that carry state where subscribers only need waking when it changes.
+    /// The first call always broadcasts. Like `broadcast_ordered()`, this holds exclusive access
+    /// to the bus so concurrent calls can't both broadcast the same change. Returns whether the
+    /// event was broadcast.
+    pub fn broadcast_if_changed(&self, event: T) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut changed = false;
+
+        self.broadcast_exclusive_with(|inner| {
+            let last_event = inner.last_event.get_mut().expect("Lock was poisoned");
+            if last_event.as_ref() == Some(&event) {
+                return (0, Vec::new());
+            }
+
+            changed = true;
+            *last_event = Some(event.clone());
+            inner.broadcast(event)
+        });
+
+        changed
+    }
+
+    fn broadcast_exclusive_with<F>(&self, broadcast: F) -> usize
+    where
+        F: FnOnce(&mut BusInner<T>) -> (usize, Vec<usize>),
+    {
+        match self.try_broadcast_exclusive_with(broadcast) {
+            Ok(delivered) => delivered,
+            Err(BroadcastError::Closed) => 0,
+            Err(_) => panic!("Lock was poisoned"),
+        }
+    }
+
+    fn try_broadcast_exclusive_with<F>(&self, broadcast: F) -> Result<usize, BroadcastError>
+    where
+        F: FnOnce(&mut BusInner<T>) -> (usize, Vec<usize>),
+    {
+        let (delivered, removed, on_disconnect) = {
+            let mut inner = self.inner.write().map_err(|_| BroadcastError::Poisoned)?;
+            if inner.closed {
+                return Err(BroadcastError::Closed);
+            }
+            let (delivered, disconnected) = broadcast(&mut inner);
+
+            // Prune while we still have exclusive access
+            let removed = if disconnected.is_empty() {
+                disconnected
+            } else {
+                inner.prune(&disconnected)
+            };
+
+            (delivered, removed, inner.on_disconnect.clone())
+        };
+
+        report_disconnected(on_disconnect, removed);
+        Ok(delivered)
+    }
+
+    /// Sets a callback that is called with the id of each disconnected `Receiver` pruned by
+    /// `broadcast()`
+    ///
+    /// Only one callback is supported so this replaces any previous callback. `Receiver`s removed
+    /// by `remove_rx()` or `clear()` are not reported.
+    pub fn on_disconnect(&self, callback: Box<dyn Fn(usize) + Send + Sync>) {
+        self.inner.write().expect("Lock was poisoned").on_disconnect = Some(callback.into());
+    }
+
+    /// Sets a hook that is called with a reference to each event before it's sent to any
+    /// `Receiver`s
+    ///
+    /// The hook is called once per broadcast event, even if there are no `Receiver`s, which
+    /// makes it suitable for tracing or sampling. It's not called by `broadcast_fn()` as there's
+    /// no single event. The hook runs while the bus is locked so it must not call back into the
+    /// bus. Only one hook is supported so this replaces any previous hook.
+    pub fn set_pre_broadcast_hook(&self, hook: Box<dyn Fn(&T) + Send + Sync>) {
+        self.inner.write().expect("Lock was poisoned").pre_broadcast = Some(hook);
+    }
+
+    /// Adds a tap that is called with a reference to each event on the broadcasting thread
+    ///
+    /// Taps see the same events as the pre-broadcast hook, so they miss events from
+    /// `broadcast_fn()`, and are called after it in the order they were added. Unlike `Receiver`s, they're called synchronously so nothing is buffered,
+    /// which suits teeing events into a log. Like the hook, taps run while the bus is locked so
+    /// they must not call back into the bus and slow taps slow down every broadcast.
+    pub fn add_tap(&self, tap: Box<dyn Fn(&T) + Send + Sync>) {
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .taps
+            .push(tap);
+    }
+
+    /// Returns the number of registered `Receiver`s
+    ///
+    /// This includes `Receiver`s that have been dropped but not yet pruned. Disconnected
+    /// `Sender`s are only reaped on the next call to `broadcast()`. See the
+    /// [Design](crate#design) section for why.
+    pub fn subscriber_count(&self) -> usize {
+        self.inner
+            .read()
+            .expect("Lock was poisoned")
+            .core
+            .senders
+            .len()
+    }
+
+    /// Returns the number of handles to this bus, including this one
+    ///
+    /// Cloning a `Bus` only clones an `Arc`, so every clone shares the same `Receiver`s. This
+    /// counts every clone along with the `BusSender`s and `BusReceiver`s created from them and
+    /// any clones held by threads, such as those started by `map()` or `connect_to()`.
+    /// `Receiver`s are disconnected once the count reaches zero.
+    pub fn producer_count(&self) -> usize {
+        Arc::strong_count(&self.inner)
+    }
+
+    /// Returns `true` if `other` is a handle to the same bus as this one
+    ///
+    /// Clones of a `Bus` are the same bus whereas two buses created separately never are.
+    pub fn same_bus(&self, other: &Bus<T>) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns the ids of the registered `Receiver`s in ascending order
+    ///
+    /// Like `subscriber_count()`, this includes `Receiver`s that have been dropped but not yet
+    /// pruned.
+    pub fn subscriber_ids(&self) -> Vec<usize> {
+        let inner = self.inner.read().expect("Lock was poisoned");
+        let mut ids = inner
+            .core
+            .senders
+            .iter()
+            .map(|subscriber| subscriber.id)
+            .collect::<Vec<_>>();
+        // Subscribers are sorted by priority first
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Calls `f` with the id of each registered `Receiver` in the order they're sent events
+    ///
+    /// Unlike `subscriber_ids()`, this doesn't allocate. `f` is called while the bus is locked,
+    /// so every id belongs to the same set of `Receiver`s, but `f` must not call back into the
+    /// bus. Like `subscriber_count()`, this includes `Receiver`s that have been dropped but not
+    /// yet pruned.
+    pub fn for_each_subscriber_id<F: FnMut(usize)>(&self, mut f: F) {
+        let inner = self.inner.read().expect("Lock was poisoned");
+        for subscriber in &inner.core.senders {
+            f(subscriber.id);
+        }
+    }
+
+    /// Returns the id that will be given to the next `Receiver` added to the bus
+    ///
+    /// Every `Receiver` gets a unique id, starting from zero and incrementing by one each time a
+    /// `Receiver` is added. Ids are not reused until `usize::MAX` `Receiver`s have been added and
+    /// the id wraps around, after which any id still in use is skipped. This means an `RxHandle`
+    /// can never refer to a different `Receiver` than the one it was created for while that
+    /// `Receiver` is registered. This is intended for diagnostics.
+    pub fn peek_next_id(&self) -> usize {
+        self.inner.read().expect("Lock was poisoned").core.next_id
+    }
+
+    /// Returns `true` if there are no registered `Receiver`s
+    ///
+    /// See `subscriber_count()` for caveats about disconnected `Receiver`s.
+    pub fn is_empty(&self) -> bool {
+        self.subscriber_count() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<U> Bus<Arc<U>> {
+    /// Wraps `value` in an `Arc` and broadcasts it to all `Receiver`s
+    ///
+    /// Subscribers receive an `Arc<U>` and only the pointer is cloned for each `Receiver`, so
+    /// large immutable events are never deep-cloned. Returns the number of `Receiver`s the event
+    /// was delivered to.
+    pub fn broadcast_shared(&self, value: U) -> usize {
+        self.broadcast(Arc::new(value))
+    }
+}
+
+#[cfg(feature = "std")]
+/// Called without holding the lock so the callback can use the bus. Concurrent broadcasts can
+/// detect the same disconnection so only the one that removed the sender reports it.
+fn report_disconnected(on_disconnect: Option<DisconnectFn>, removed: Vec<usize>) {
+    if let Some(on_disconnect) = on_disconnect {
+        for id in removed {
+            on_disconnect(id);
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl<T: Clone> Default for Bus<T> {
     fn default() -> Self {
         Bus::new()
     }
 }
 
+#[cfg(feature = "std")]
+/// Buses are equal if they are handles to the same bus as with `Bus::same_bus()`
+///
+/// The events that have been broadcast aren't compared.
+impl<T: Clone> PartialEq for Bus<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_bus(other)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone> Eq for Bus<T> {}
+
+#[cfg(feature = "std")]
+/// Broadcasts each item in order as with `Bus::broadcast_all()`
+impl<T: Clone> Extend<T> for Bus<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.broadcast_all(iter);
+    }
+}
+
+#[cfg(feature = "std")]
+/// Adds a new `Receiver` and iterates over its events, blocking until each arrives
+///
+/// This is sugar for `bus.add_rx().into_iter()` so every `for event in &bus` loop registers a
+/// new subscriber. The iterator ends once every handle to the bus has been dropped.
+impl<T: Clone> IntoIterator for &Bus<T> {
+    type Item = T;
+    type IntoIter = crossbeam::channel::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.add_rx().into_iter()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone> fmt::Debug for Bus<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.read().expect("Lock was poisoned");
+        f.debug_struct("Bus")
+            .field("subscribers", &inner.core.senders.len())
+            .field("next_id", &inner.core.next_id)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
 type BoxedFn<T> = Box<dyn FnMut(T) + Send>;
 
-struct DropSignal {
-    tx_signal: Sender<()>,
+#[cfg(feature = "std")]
+enum Signal {
+    Terminate,
+    TerminateDraining,
+    // Pass any buffered events to the callback and then acknowledge
+    Flush(Sender<()>),
 }
 
+#[cfg(feature = "std")]
+enum DropSignal {
+    Worker(Sender<Signal>),
+    // Passed to `Subscription::new()`, which only supports terminating
+    Unit(Sender<()>),
+}
+
+#[cfg(feature = "std")]
 impl DropSignal {
-    pub fn new(tx_signal: Sender<()>) -> Arc<Self> {
-        Arc::new(DropSignal { tx_signal })
+    /// Returns `false` if the signal couldn't be sent
+    fn send(&self, signal: Signal) -> bool {
+        match (self, signal) {
+            (DropSignal::Worker(tx_signal), signal) => tx_signal.send(signal).is_ok(),
+            (DropSignal::Unit(_), Signal::Flush(_)) => false,
+            (DropSignal::Unit(tx_signal), _) => tx_signal.send(()).is_ok(),
+        }
     }
 }
 
+#[cfg(feature = "std")]
 impl Drop for DropSignal {
     fn drop(&mut self) {
-        let _ = self.tx_signal.send(());
+        self.send(Signal::Terminate);
+    }
+}
+
+/// What runs a subscription's callback
+#[cfg(feature = "std")]
+pub(crate) enum Worker {
+    Thread(JoinHandle<()>),
+    #[cfg(feature = "async")]
+    Task(tokio::task::JoinHandle<()>),
+}
+
+#[cfg(feature = "std")]
+impl Worker {
+    fn join(self) -> thread::Result<()> {
+        match self {
+            Worker::Thread(thread) => thread.join(),
+            #[cfg(feature = "async")]
+            Worker::Task(task) => task::wait_for(task),
+        }
     }
 }
 
+#[cfg(feature = "std")]
 #[derive(Clone)]
 pub struct Subscription {
     terminate: Arc<DropSignal>,
+    worker: Arc<Mutex<Option<Worker>>>,
+    alive: Arc<AtomicBool>,
+    handle: Option<RxHandle>,
 }
 
+#[cfg(feature = "std")]
 impl Subscription {
+    /// Creates a `Subscription` that sends `()` to `terminate` once it and every clone have been
+    /// dropped
+    ///
+    /// This lets you manage your own subscription thread. `dispose()`, `dispose_draining()` and
+    /// `join()` also send `()`, `flush()` returns straight away and `is_alive()` is always
+    /// `true`.
     pub fn new(terminate: Sender<()>) -> Self {
         Subscription {
-            terminate: DropSignal::new(terminate),
+            terminate: Arc::new(DropSignal::Unit(terminate)),
+            worker: Arc::new(Mutex::new(None)),
+            alive: Arc::new(AtomicBool::new(true)),
+            handle: None,
         }
     }
-}
 
-pub trait SubscribeToReader<T: Send + 'static> {
-    #[must_use]
-    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription;
-    fn subscribe(&self, callback: BoxedFn<T>);
-}
+    pub(crate) fn from_worker(
+        terminate: Sender<Signal>,
+        worker: Worker,
+        alive: Arc<AtomicBool>,
+    ) -> Self {
+        Subscription {
+            terminate: Arc::new(DropSignal::Worker(terminate)),
+            worker: Arc::new(Mutex::new(Some(worker))),
+            alive,
+            handle: None,
+        }
+    }
 
-impl<T: Send + 'static> SubscribeToReader<T> for Receiver<T> {
-    #[must_use]
-    fn subscribe_on_thread(&self, mut callback: BoxedFn<T>) -> Subscription {
-        let (terminate_tx, terminate_rx) = bounded::<()>(0);
-        let receiver = self.clone();
+    pub(crate) fn with_handle(mut self, handle: RxHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
 
-        thread::Builder::new()
-            .name("Receiver subscription thread".to_string())
-            .spawn(move || loop {
-                for event in receiver.try_iter() {
-                    callback(event);
-                }
+    /// Returns the handle of the `Receiver` this subscription was created with
+    ///
+    /// This can be passed to `Bus::broadcast_except()` or `Bus::remove_rx()`. It's `None` for
+    /// subscriptions created from a plain `Receiver` or from several buses as there's no single
+    /// `Receiver` the bus knows about.
+    pub fn handle(&self) -> Option<RxHandle> {
+        self.handle
+    }
 
-                match terminate_rx.try_recv() {
-                    Err(TryRecvError::Empty) => {}
-                    _ => return,
-                }
-            })
-            .expect("Could not start Receiver subscription thread");
+    /// Returns the id of the `Receiver` this subscription was created with
+    ///
+    /// This matches the ids returned by `Bus::subscriber_ids()` and passed to the callback set
+    /// with `Bus::on_disconnect()`. Like `handle()`, it's `None` when there's no single
+    /// `Receiver` the bus knows about.
+    pub fn id(&self) -> Option<usize> {
+        self.handle.map(|handle| handle.id)
+    }
 
-        Subscription::new(terminate_tx)
+    /// Returns `false` once the subscription thread has exited
+    ///
+    /// The thread exits when the callback panics, when the bus is dropped or when the
+    /// subscription is terminated. A panic is still reported by `join()`.
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Acquire)
     }
 
-    fn subscribe(&self, mut callback: BoxedFn<T>) {
-        for event in self.iter() {
-            callback(event);
+    /// Terminates the subscription thread and waits for it to exit
+    ///
+    /// If the callback is running, this waits for it to return. Returns an error if the callback
+    /// panicked. If this `Subscription` has been cloned, only the first call to `join()` waits for
+    /// the thread.
+    pub fn join(self) -> thread::Result<()> {
+        self.terminate.send(Signal::Terminate);
+
+        let worker = self.worker.lock().expect("Lock was poisoned").take();
+        match worker {
+            Some(worker) => worker.join(),
+            None => Ok(()),
         }
     }
-}
 
-impl<T: Clone + Send + 'static> SubscribeToReader<T> for Bus<T> {
-    #[must_use]
-    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription {
-        self.add_rx().subscribe_on_thread(callback)
+    /// Terminates the subscription thread
+    ///
+    /// Unlike dropping the `Subscription`, this terminates the thread even if the `Subscription`
+    /// has been cloned. Events still buffered may be discarded.
+    pub fn dispose(&self) {
+        self.terminate.send(Signal::Terminate);
     }
 
-    fn subscribe(&self, callback: BoxedFn<T>) {
-        self.add_rx().subscribe(callback)
+    /// Terminates the subscription thread after passing any buffered events to the callback
+    ///
+    /// Every event broadcast before this is called is passed to the callback before the thread
+    /// exits. Without this, events still buffered when the `Subscription` is dropped may be
+    /// discarded.
+    pub fn dispose_draining(&self) {
+        self.terminate.send(Signal::TerminateDraining);
+    }
+
+    /// Blocks until every event broadcast before this was called has been passed to the
+    /// callback
+    ///
+    /// Returns straight away if the subscription thread has exited.
+    pub fn flush(&self) {
+        let (ack_tx, ack_rx) = bounded(1);
+        if self.terminate.send(Signal::Flush(ack_tx)) {
+            // Fails if the thread exits before acknowledging
+            let _ = ack_rx.recv();
+        }
+    }
+
+    /// Lets the subscription thread run until the bus is dropped without holding on to the
+    /// `Subscription`
+    ///
+    /// The signal that would terminate the thread when the last `Subscription` is dropped is
+    /// leaked, so the thread only exits once every handle to the bus has been dropped or the
+    /// callback panics. A small allocation is leaked for every detached subscription and it is
+    /// never freed, even after the thread exits. Other clones of this `Subscription` can still
+    /// terminate the thread with `dispose()` or `join()`.
+    pub fn detach(self) {
+        std::mem::forget(self.terminate.clone());
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crossbeam::channel::RecvTimeoutError;
-    use std::time::Duration;
+#[cfg(feature = "std")]
+pub trait SubscribeToReader<T: Send + 'static> {
+    #[must_use]
+    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription;
+    fn subscribe(&self, callback: BoxedFn<T>);
 
-    #[derive(Clone, PartialEq, Debug)]
-    struct Something;
+    /// Like `subscribe` but returns once `should_stop` returns `true`
+    ///
+    /// `should_stop` is checked after each event is passed to `callback`.
+    fn subscribe_until(&self, callback: BoxedFn<T>, should_stop: Box<dyn Fn() -> bool + Send>);
 
-    #[derive(Clone, PartialEq, Debug)]
-    enum Event {
-        Start,
-        Stop(Vec<Something>),
+    /// Like `subscribe` but also returns once no events have arrived for `idle`
+    ///
+    /// Unlike the bus being dropped, this means the bus is still alive but quiet. Events
+    /// broadcast after this returns stay buffered until the next subscription.
+    fn subscribe_idle_timeout(&self, idle: Duration, callback: BoxedFn<T>);
+
+    /// Like `subscribe_on_thread` but each event is transformed by `map` on the subscription
+    /// thread before being passed to `callback`
+    #[must_use]
+    fn subscribe_mapped<U: 'static>(
+        &self,
+        map: Box<dyn Fn(T) -> U + Send>,
+        callback: BoxedFn<U>,
+    ) -> Subscription;
+
+    /// Like `subscribe` but accepts any closure rather than a `Box`
+    fn subscribe_fn<F>(&self, callback: F)
+    where
+        F: FnMut(T) + Send + 'static,
+    {
+        self.subscribe(Box::new(callback))
     }
 
-    #[test]
-    fn subscribe_on_thread() {
-        let dispatcher = Bus::<Event>::new();
+    /// Like `subscribe_on_thread` but accepts any closure rather than a `Box`
+    #[must_use]
+    fn subscribe_on_thread_fn<F>(&self, callback: F) -> Subscription
+    where
+        F: FnMut(T) + Send + 'static,
+    {
+        self.subscribe_on_thread(Box::new(callback))
+    }
 
-        // Ensure multiple subscriptions work
-        let _sub_unused = dispatcher.subscribe_on_thread(Box::new(move |_event| {
-            // But do nothing
-        }));
+    /// Calls `callback` on every broadcast from another thread that runs until the bus is dropped
+    ///
+    /// Unlike `subscribe_on_thread`, there's no `Subscription` to hold on to. The thread exits
+    /// once every `Bus` handle has been dropped and any buffered events have been passed to
+    /// `callback`.
+    fn subscribe_detached(&self, callback: BoxedFn<T>) -> JoinHandle<()>;
 
-        let __sub_unused = dispatcher.subscribe_on_thread(Box::new(move |_event| {
-            // But do nothing
-        }));
+    /// Calls `callback` with the next event from another thread and then terminates the
+    /// subscription
+    #[must_use]
+    fn subscribe_once(&self, callback: Box<dyn FnOnce(T) + Send>) -> Subscription;
 
-        let (tx_test, rx_test) = unbounded::<Event>();
+    /// Like `subscribe_on_thread` but the subscription terminates once `callback` returns
+    /// `false`
+    ///
+    /// No further events are passed to `callback` and its `Receiver` is disconnected from the
+    /// bus.
+    #[must_use]
+    fn subscribe_while(&self, callback: Box<dyn FnMut(T) -> bool + Send>) -> Subscription;
 
-        {
-            let _sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
-                tx_test.send(event).unwrap();
-            }));
+    /// Like `subscribe_on_thread` but `callback` is skipped for events equal to the previously
+    /// delivered event
+    ///
+    /// The first event is always delivered.
+    #[must_use]
+    fn subscribe_distinct(&self, mut callback: BoxedFn<T>) -> Subscription
+    where
+        T: PartialEq + Clone,
+    {
+        let mut last = None;
+
+        self.subscribe_on_thread(Box::new(move |event| {
+            if last.as_ref() != Some(&event) {
+                last = Some(event.clone());
+                callback(event);
+            }
+        }))
+    }
+
+    /// Like `subscribe_on_thread` but buffered events are passed to `callback` in batches of up
+    /// to `max_batch` events
+    ///
+    /// Batches are delivered as soon as any events are available rather than waiting for a full
+    /// batch. Panics if `max_batch` is zero.
+    #[must_use]
+    fn subscribe_batched(
+        &self,
+        max_batch: usize,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription;
+
+    /// Like `subscribe_batched` but events are collected and passed to `callback` once every
+    /// `window`
+    ///
+    /// If no events arrived during a window, `callback` is passed an empty `Vec` unless
+    /// `skip_empty` is `true`. Any events collected when the bus is dropped are passed to
+    /// `callback` before the thread exits.
+    #[must_use]
+    fn subscribe_windowed(
+        &self,
+        window: Duration,
+        skip_empty: bool,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription;
+
+    /// Like `subscribe_on_thread` but only the newest buffered event is passed to `callback`
+    ///
+    /// Events that arrive while `callback` is running are coalesced so `callback` is next passed
+    /// the last of them and the others are discarded.
+    #[must_use]
+    fn subscribe_latest(&self, callback: BoxedFn<T>) -> Subscription;
+
+    /// Like `subscribe_latest` but events are coalesced per key rather than globally
+    ///
+    /// Of the events buffered while `callback` is running, only the last for each key returned
+    /// by `key_fn` is passed to `callback`. Keys are delivered in the order they were first seen.
+    #[must_use]
+    fn subscribe_coalesced<K, F>(&self, key_fn: F, callback: BoxedFn<T>) -> Subscription
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K + Send + 'static;
+
+    /// Like `subscribe_on_thread` but the callback can be replaced without restarting the
+    /// subscription thread
+    ///
+    /// See `ReplaceableSubscription::set_callback()`.
+    #[must_use]
+    fn subscribe_replaceable(&self, callback: BoxedFn<T>) -> ReplaceableSubscription<T> {
+        let callback = Arc::new(Mutex::new(callback));
+        let thread_callback = callback.clone();
+
+        let subscription = self.subscribe_on_thread(Box::new(move |event| {
+            (thread_callback.lock().expect("Lock was poisoned"))(event);
+        }));
+
+        ReplaceableSubscription::new(subscription, callback)
+    }
+
+    /// Like `subscribe_on_thread` but every event is folded into `init` by `f`
+    ///
+    /// The final state can be recovered from the returned `FoldingSubscription` with
+    /// `into_state()`.
+    #[must_use]
+    fn subscribe_folding<S, F>(&self, init: S, mut f: F) -> FoldingSubscription<S>
+    where
+        S: Send + 'static,
+        F: FnMut(&mut S, T) + Send + 'static,
+    {
+        let state = Arc::new(Mutex::new(init));
+        let thread_state = state.clone();
+
+        let subscription = self.subscribe_on_thread(Box::new(move |event| {
+            f(&mut thread_state.lock().expect("Lock was poisoned"), event);
+        }));
+
+        FoldingSubscription::new(subscription, state)
+    }
+
+    /// Like `subscribe_on_thread` but `callback` is called at most once per `min_interval`
+    ///
+    /// Events that arrive less than `min_interval` after the last one passed to `callback` are
+    /// dropped. The first event is always delivered.
+    #[must_use]
+    fn subscribe_throttled(
+        &self,
+        min_interval: Duration,
+        mut callback: BoxedFn<T>,
+    ) -> Subscription {
+        let mut last: Option<Instant> = None;
+
+        self.subscribe_on_thread(Box::new(move |event| {
+            let now = Instant::now();
+            let due = match last {
+                Some(last) => now.duration_since(last) >= min_interval,
+                None => true,
+            };
+            if due {
+                last = Some(now);
+                callback(event);
+            }
+        }))
+    }
+
+    /// Like `subscribe_on_thread` but `callback` is only called for events where `predicate`
+    /// returns `true`
+    #[must_use]
+    fn subscribe_filtered(
+        &self,
+        predicate: Box<dyn Fn(&T) -> bool + Send>,
+        mut callback: BoxedFn<T>,
+    ) -> Subscription {
+        self.subscribe_on_thread(Box::new(move |event| {
+            if predicate(&event) {
+                callback(event);
+            }
+        }))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Send + 'static> SubscribeToReader<T> for Receiver<T> {
+    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription {
+        SubscriptionBuilder::new().subscribe_on_thread(self, callback)
+    }
+
+    fn subscribe(&self, mut callback: BoxedFn<T>) {
+        for event in self.iter() {
+            callback(event);
+        }
+    }
+
+    fn subscribe_until(&self, mut callback: BoxedFn<T>, should_stop: Box<dyn Fn() -> bool + Send>) {
+        for event in self.iter() {
+            callback(event);
+
+            if should_stop() {
+                return;
+            }
+        }
+    }
+
+    fn subscribe_idle_timeout(&self, idle: Duration, mut callback: BoxedFn<T>) {
+        // Returns on a timeout or once the bus has been dropped
+        while let Ok(event) = self.recv_timeout(idle) {
+            callback(event);
+        }
+    }
+
+    fn subscribe_detached(&self, mut callback: BoxedFn<T>) -> JoinHandle<()> {
+        let receiver = self.clone();
+
+        thread::Builder::new()
+            .name(next_thread_name("sub"))
+            .spawn(move || {
+                for event in receiver.iter() {
+                    callback(event);
+                }
+            })
+            .expect("Could not start Receiver detached subscription thread")
+    }
+
+    fn subscribe_mapped<U: 'static>(
+        &self,
+        map: Box<dyn Fn(T) -> U + Send>,
+        mut callback: BoxedFn<U>,
+    ) -> Subscription {
+        SubscriptionBuilder::new()
+            .name(next_thread_name("mapped-sub"))
+            .spawn(
+                self.clone(),
+                "Receiver mapped subscription thread",
+                move |event, _| {
+                    callback(map(event));
+                    true
+                },
+            )
+    }
+
+    fn subscribe_once(&self, callback: Box<dyn FnOnce(T) + Send>) -> Subscription {
+        // FnOnce can't be called from the loop so it's taken out on the first event
+        let mut callback = Some(callback);
+
+        SubscriptionBuilder::new().spawn(
+            self.clone(),
+            "Receiver once subscription thread",
+            move |event, _| {
+                if let Some(callback) = callback.take() {
+                    callback(event);
+                }
+                false
+            },
+        )
+    }
+
+    fn subscribe_while(&self, mut callback: Box<dyn FnMut(T) -> bool + Send>) -> Subscription {
+        SubscriptionBuilder::new().spawn(
+            self.clone(),
+            "Receiver while subscription thread",
+            move |event, _| callback(event),
+        )
+    }
+
+    fn subscribe_batched(
+        &self,
+        max_batch: usize,
+        mut callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        assert!(max_batch > 0, "max_batch must be greater than zero");
+
+        SubscriptionBuilder::new().spawn(
+            self.clone(),
+            "Receiver batched subscription thread",
+            move |event, receiver| {
+                let mut batch = Vec::with_capacity(max_batch);
+                batch.push(event);
+                batch.extend(receiver.try_iter().take(max_batch - 1));
+                callback(batch);
+                true
+            },
+        )
+    }
+
+    fn subscribe_windowed(
+        &self,
+        window: Duration,
+        skip_empty: bool,
+        mut callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        let receiver = self.clone();
+
+        SubscriptionBuilder::new().spawn_with(
+            "Receiver windowed subscription thread",
+            move |terminate_rx| {
+                let mut batch = Vec::new();
+                let mut deadline = Instant::now() + window;
+
+                loop {
+                    select! {
+                        recv(receiver) -> event => match event {
+                            Ok(event) => batch.push(event),
+                            // The bus has been dropped so pass on what's left
+                            Err(_) => {
+                                if !batch.is_empty() {
+                                    callback(batch);
+                                }
+                                return;
+                            }
+                        },
+                        recv(terminate_rx) -> signal => match signal {
+                            Ok(Signal::Flush(ack)) => {
+                                batch.extend(receiver.try_iter());
+                                if !batch.is_empty() {
+                                    callback(mem::take(&mut batch));
+                                }
+                                let _ = ack.send(());
+                            }
+                            Ok(Signal::TerminateDraining) => {
+                                batch.extend(receiver.try_iter());
+                                if !batch.is_empty() {
+                                    callback(batch);
+                                }
+                                return;
+                            }
+                            _ => return,
+                        },
+                        default(deadline.saturating_duration_since(Instant::now())) => {
+                            if !(skip_empty && batch.is_empty()) {
+                                callback(mem::take(&mut batch));
+                            }
+
+                            // Start the next window from now if the callback overran this one
+                            deadline += window;
+                            let now = Instant::now();
+                            if deadline < now {
+                                deadline = now + window;
+                            }
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    fn subscribe_latest(&self, mut callback: BoxedFn<T>) -> Subscription {
+        SubscriptionBuilder::new().spawn(
+            self.clone(),
+            "Receiver latest subscription thread",
+            move |event, receiver| {
+                callback(receiver.try_iter().last().unwrap_or(event));
+                true
+            },
+        )
+    }
+
+    fn subscribe_coalesced<K, F>(&self, key_fn: F, mut callback: BoxedFn<T>) -> Subscription
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K + Send + 'static,
+    {
+        SubscriptionBuilder::new().spawn(
+            self.clone(),
+            "Receiver coalesced subscription thread",
+            move |event, receiver| {
+                let mut indexes = HashMap::new();
+                let mut latest = Vec::new();
+
+                for event in iter::once(event).chain(receiver.try_iter()) {
+                    match indexes.entry(key_fn(&event)) {
+                        Entry::Occupied(entry) => latest[*entry.get()] = event,
+                        Entry::Vacant(entry) => {
+                            entry.insert(latest.len());
+                            latest.push(event);
+                        }
+                    }
+                }
+
+                for event in latest {
+                    callback(event);
+                }
+                true
+            },
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone + Send + 'static> Bus<T> {
+    /// Subscribes to a new `Receiver` and records its handle in the `Subscription`
+    fn subscribe_with_handle<F>(&self, subscribe: F) -> Subscription
+    where
+        F: FnOnce(&Receiver<T>) -> Subscription,
+    {
+        let (receiver, handle) = self.add_rx_with_handle();
+        subscribe(&receiver).with_handle(handle)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone + Send + 'static> SubscribeToReader<T> for Bus<T> {
+    fn subscribe_on_thread(&self, callback: BoxedFn<T>) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_on_thread(callback))
+    }
+
+    fn subscribe(&self, callback: BoxedFn<T>) {
+        self.add_rx().subscribe(callback)
+    }
+
+    fn subscribe_until(&self, callback: BoxedFn<T>, should_stop: Box<dyn Fn() -> bool + Send>) {
+        self.add_rx().subscribe_until(callback, should_stop)
+    }
+
+    fn subscribe_idle_timeout(&self, idle: Duration, callback: BoxedFn<T>) {
+        self.add_rx().subscribe_idle_timeout(idle, callback)
+    }
+
+    fn subscribe_detached(&self, callback: BoxedFn<T>) -> JoinHandle<()> {
+        self.add_rx().subscribe_detached(callback)
+    }
+
+    fn subscribe_mapped<U: 'static>(
+        &self,
+        map: Box<dyn Fn(T) -> U + Send>,
+        callback: BoxedFn<U>,
+    ) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_mapped(map, callback))
+    }
+
+    fn subscribe_once(&self, callback: Box<dyn FnOnce(T) + Send>) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_once(callback))
+    }
+
+    fn subscribe_while(&self, callback: Box<dyn FnMut(T) -> bool + Send>) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_while(callback))
+    }
+
+    fn subscribe_batched(
+        &self,
+        max_batch: usize,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_batched(max_batch, callback))
+    }
+
+    fn subscribe_windowed(
+        &self,
+        window: Duration,
+        skip_empty: bool,
+        callback: Box<dyn FnMut(Vec<T>) + Send>,
+    ) -> Subscription {
+        self.subscribe_with_handle(|receiver| {
+            receiver.subscribe_windowed(window, skip_empty, callback)
+        })
+    }
+
+    fn subscribe_latest(&self, callback: BoxedFn<T>) -> Subscription {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_latest(callback))
+    }
+
+    fn subscribe_coalesced<K, F>(&self, key_fn: F, callback: BoxedFn<T>) -> Subscription
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K + Send + 'static,
+    {
+        self.subscribe_with_handle(|receiver| receiver.subscribe_coalesced(key_fn, callback))
+    }
+}
+
+#[cfg(feature = "std")]
+/// Configures the thread a subscription runs on
+///
+/// ```
+/// use double_decker::{Bus, SubscriptionBuilder};
+///
+/// let bus = Bus::<u32>::new();
+///
+/// let _subscription = SubscriptionBuilder::new()
+///     .name("Audio events".to_string())
+///     .stack_size(64 * 1024)
+///     .subscribe_on_thread(&bus.add_rx(), Box::new(|event| println!("{}", event)));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SubscriptionBuilder {
+    name: Option<String>,
+    stack_size: Option<usize>,
+    mode: WorkerMode,
+}
+
+#[cfg(feature = "std")]
+impl SubscriptionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names the subscription thread
+    ///
+    /// Defaults to a unique name such as "double_decker-sub-3".
+    pub fn name(mut self, name: String) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Sets the stack size of the subscription thread in bytes
+    ///
+    /// Defaults to the standard library's default stack size.
+    pub fn stack_size(mut self, size: usize) -> Self {
+        self.stack_size = Some(size);
+        self
+    }
+
+    /// Sets how the subscription thread waits for events
+    ///
+    /// Defaults to `WorkerMode::Park`. Spinning can reduce latency for latency sensitive
+    /// subscribers at the cost of keeping a CPU core busy.
+    pub fn worker_mode(mut self, mode: WorkerMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Calls `callback` on every event from `receiver` on a thread configured by this builder
+    ///
+    /// Behaves like `SubscribeToReader::subscribe_on_thread`.
+    #[must_use]
+    pub fn subscribe_on_thread<T: Send + 'static>(
+        self,
+        receiver: &Receiver<T>,
+        mut callback: BoxedFn<T>,
+    ) -> Subscription {
+        self.spawn(
+            receiver.clone(),
+            "Receiver subscription thread",
+            move |event, _| {
+                callback(event);
+                true
+            },
+        )
+    }
+
+    /// Spawns a subscription thread that passes events to `callback` until it returns `false`,
+    /// the `Subscription` is dropped or the bus is dropped
+    ///
+    /// `callback` is also passed the `Receiver` so it can take any further buffered events.
+    /// `kind` describes the subscription if the thread can't be started.
+    fn spawn<T, F>(self, receiver: Receiver<T>, kind: &str, callback: F) -> Subscription
+    where
+        T: Send + 'static,
+        F: FnMut(T, &Receiver<T>) -> bool + Send + 'static,
+    {
+        let mode = self.mode;
+        self.spawn_with(kind, move |terminate_rx| {
+            run_subscription(receiver, terminate_rx, mode, callback)
+        })
+    }
+
+    /// Spawns a subscription thread that runs `run` until it returns
+    ///
+    /// `run` is passed the `Receiver` for the signals sent by the `Subscription`. `kind` describes
+    /// the subscription if the thread can't be started.
+    pub(crate) fn spawn_with<F>(self, kind: &str, run: F) -> Subscription
+    where
+        F: FnOnce(Receiver<Signal>) + Send + 'static,
+    {
+        let (terminate_tx, terminate_rx) = bounded::<Signal>(0);
+
+        let name = self.name.unwrap_or_else(|| next_thread_name("sub"));
+        let mut builder = thread::Builder::new().name(name);
+        if let Some(size) = self.stack_size {
+            builder = builder.stack_size(size);
+        }
+
+        let alive = Arc::new(AtomicBool::new(true));
+        let thread_alive = alive.clone();
+
+        let thread = builder
+            .spawn(move || run_until_exit(thread_alive, move || run(terminate_rx)))
+            .unwrap_or_else(|_| panic!("Could not start {}", kind));
+
+        Subscription::from_worker(terminate_tx, Worker::Thread(thread), alive)
+    }
+}
+
+/// Returns a unique name for a subscription thread so they can be told apart in debuggers
+///
+/// `kind` distinguishes the different types of subscription, eg. "sub" or "mapped-sub".
+#[cfg(feature = "std")]
+pub(crate) fn next_thread_name(kind: &str) -> String {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    format!(
+        "double_decker-{}-{}",
+        kind,
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+/// Runs `run` and then clears `alive`, even if `run` panics
+#[cfg(feature = "std")]
+pub(crate) fn run_until_exit<F: FnOnce()>(alive: Arc<AtomicBool>, run: F) {
+    // The callback is only called from this thread and the thread exits if it panics, so nothing
+    // can observe it in a broken state
+    let result = panic::catch_unwind(AssertUnwindSafe(run));
+
+    alive.store(false, Ordering::Release);
+
+    // Pass the panic on so it's reported by `Subscription::join()`
+    if let Err(payload) = result {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Passes events from `receiver` to `callback` until it returns `false`, a signal terminates the
+/// subscription or the bus is dropped
+#[cfg(feature = "std")]
+pub(crate) fn run_subscription<T, F>(
+    receiver: Receiver<T>,
+    terminate_rx: Receiver<Signal>,
+    mode: WorkerMode,
+    mut callback: F,
+) where
+    F: FnMut(T, &Receiver<T>) -> bool,
+{
+    loop {
+        match mode.next(&receiver, &terminate_rx) {
+            Next::Event(Ok(event)) => {
+                if !callback(event, &receiver) {
+                    return;
+                }
+            }
+            // The bus has been dropped so there will be no more events
+            Next::Event(Err(_)) => return,
+            Next::Signal(Ok(Signal::Flush(ack))) => {
+                while let Ok(event) = receiver.try_recv() {
+                    if !callback(event, &receiver) {
+                        return;
+                    }
+                }
+                let _ = ack.send(());
+            }
+            Next::Signal(Ok(Signal::TerminateDraining)) => {
+                while let Ok(event) = receiver.try_recv() {
+                    if !callback(event, &receiver) {
+                        return;
+                    }
+                }
+                return;
+            }
+            Next::Signal(_) => return,
+        }
+    }
+}
+
+// Checks at compile time that the public types can be shared between threads whenever the events
+// are `Send`. Events don't need to be `Sync` as each `Receiver` gets its own clone.
+#[cfg(feature = "std")]
+const _: fn() = || {
+    fn assert_send_sync<X: Send + Sync>() {}
+
+    fn check<T: Clone + Send + 'static>() {
+        assert_send_sync::<Bus<T>>();
+        assert_send_sync::<BusSender<T>>();
+        assert_send_sync::<BusReceiver<T>>();
+        assert_send_sync::<PollingSubscription<T>>();
+        assert_send_sync::<ReconnectingReceiver<T>>();
+        assert_send_sync::<ReplaceableSubscription<T>>();
+        assert_send_sync::<FoldingSubscription<T>>();
+        assert_send_sync::<TopicBus<String, T>>();
+        assert_send_sync::<Subscription>();
+        assert_send_sync::<SubscriptionGroup>();
+    }
+
+    let _ = check::<()>;
+};
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crossbeam::channel::{RecvTimeoutError, TryRecvError};
+    use std::time::Duration;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Something;
+
+    #[derive(Clone, PartialEq, Debug)]
+    enum Event {
+        Start,
+        Stop(Vec<Something>),
+    }
+
+    #[test]
+    fn subscribe_on_thread() {
+        let dispatcher = Bus::<Event>::new();
+
+        // Ensure multiple subscriptions work
+        let _sub_unused = dispatcher.subscribe_on_thread(Box::new(move |_event| {
+            // But do nothing
+        }));
+
+        let __sub_unused = dispatcher.subscribe_on_thread(Box::new(move |_event| {
+            // But do nothing
+        }));
+
+        let (tx_test, rx_test) = unbounded::<Event>();
+
+        {
+            let _sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+                tx_test.send(event).unwrap();
+            }));
 
             dispatcher.broadcast(Event::Start);
             dispatcher.broadcast(Event::Stop(vec![Something {}]));
@@ -337,6 +2023,774 @@ mod tests {
         }
     }
 
+    #[test]
+    fn subscribe_filtered() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let _sub = dispatcher.subscribe_filtered(
+            Box::new(|event| event % 2 == 0),
+            Box::new(move |event| {
+                tx_test.send(event).unwrap();
+            }),
+        );
+
+        for i in 0..6 {
+            dispatcher.broadcast(i);
+        }
+
+        for expected in &[0, 2, 4] {
+            match rx_test.recv_timeout(Duration::from_millis(100)) {
+                Err(_) => panic!("Event not received"),
+                Ok(e) => assert_eq!(e, *expected),
+            }
+        }
+
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn subscribe_mapped() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_test, rx_test) = unbounded::<(usize, Option<String>)>();
+
+        {
+            let _sub = dispatcher.subscribe_mapped(
+                Box::new(|event| match event {
+                    Event::Start => 0,
+                    Event::Stop(somethings) => somethings.len(),
+                }),
+                Box::new(move |len| {
+                    let name = thread::current().name().map(|n| n.to_string());
+                    tx_test.send((len, name)).unwrap();
+                }),
+            );
+
+            dispatcher.broadcast(Event::Start);
+            dispatcher.broadcast(Event::Stop(vec![Something {}, Something {}]));
+
+            for expected in &[0, 2] {
+                match rx_test.recv_timeout(Duration::from_millis(100)) {
+                    Err(_) => panic!("Event not received"),
+                    Ok((len, name)) => {
+                        assert_eq!(len, *expected);
+                        assert!(name.unwrap().starts_with("double_decker-mapped-sub-"));
+                    }
+                }
+            }
+
+            // _sub is dropped here
+        }
+
+        dispatcher.broadcast(Event::Start);
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(RecvTimeoutError::Disconnected) => {}
+            _ => panic!("Subscription has been dropped so we should not get any events"),
+        }
+    }
+
+    #[test]
+    fn dispose_draining() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+        let (tx_blocked, rx_blocked) = bounded::<()>(0);
+        let (tx_unblock, rx_unblock) = bounded::<()>(0);
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+            if event == 0 {
+                // Block the subscription thread so events back up
+                tx_blocked.send(()).unwrap();
+                rx_unblock.recv().unwrap();
+            }
+            tx_test.send(event).unwrap();
+        }));
+
+        dispatcher.broadcast(0);
+        rx_blocked.recv().unwrap();
+
+        for i in 1..10 {
+            dispatcher.broadcast(i);
+        }
+
+        tx_unblock.send(()).unwrap();
+        sub.dispose_draining();
+
+        for expected in 0..10 {
+            match rx_test.recv_timeout(Duration::from_millis(100)) {
+                Err(_) => panic!("Event not received"),
+                Ok(e) => assert_eq!(e, expected),
+            }
+        }
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(RecvTimeoutError::Disconnected) => {}
+            _ => panic!("Subscription has been disposed so we should not get any events"),
+        }
+    }
+
+    #[test]
+    fn subscribe_on_thread_after_idle() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_test, rx_test) = unbounded::<Event>();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+
+        // The subscription thread should be parked rather than spinning here
+        thread::sleep(Duration::from_millis(50));
+
+        dispatcher.broadcast(Event::Start);
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(_) => panic!("Event not received"),
+            Ok(e) => assert_eq!(e, Event::Start),
+        }
+
+        drop(sub);
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(RecvTimeoutError::Disconnected) => {}
+            _ => panic!("Subscription thread should have terminated"),
+        }
+    }
+
+    #[test]
+    fn subscribe_detached() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_test, rx_test) = unbounded::<Event>();
+
+        let handle = dispatcher.subscribe_detached(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+
+        dispatcher.broadcast(Event::Start);
+        drop(dispatcher);
+
+        // The thread exits once the bus is dropped
+        handle.join().unwrap();
+
+        assert_eq!(rx_test.try_recv(), Ok(Event::Start));
+        assert_eq!(rx_test.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn join_subscription() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_started, rx_started) = bounded::<()>(0);
+        let finished = Arc::new(Mutex::new(false));
+        let finished_cb = finished.clone();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |_event| {
+            tx_started.send(()).unwrap();
+            thread::sleep(Duration::from_millis(50));
+            *finished_cb.lock().unwrap() = true;
+        }));
+
+        dispatcher.broadcast(Event::Start);
+        rx_started.recv().unwrap();
+
+        // This waits for the running callback to return
+        sub.join().unwrap();
+        assert!(*finished.lock().unwrap());
+    }
+
+    #[test]
+    fn subscribe_once() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let sub = dispatcher.subscribe_once(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+
+        dispatcher.broadcast(1);
+        dispatcher.broadcast(2);
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(_) => panic!("Event not received"),
+            Ok(e) => assert_eq!(e, 1),
+        }
+
+        // The thread exits after the first event even though we hold the subscription
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(RecvTimeoutError::Disconnected) => {}
+            _ => panic!("Subscription should have terminated after one event"),
+        }
+
+        sub.join().unwrap();
+
+        // The receiver is disconnected so gets pruned
+        dispatcher.broadcast(3);
+        assert!(dispatcher.is_empty());
+    }
+
+    #[test]
+    fn subscribe_while() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let sub = dispatcher.subscribe_while(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+            // Zero is the sentinel
+            event != 0
+        }));
+
+        dispatcher.broadcast(1);
+        dispatcher.broadcast(0);
+        dispatcher.broadcast(2);
+
+        assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(1));
+        assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(0));
+
+        // The thread exits after the sentinel so the event after it is never delivered
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+
+        sub.join().unwrap();
+
+        dispatcher.broadcast(3);
+        assert!(dispatcher.is_empty());
+    }
+
+    #[test]
+    fn try_broadcast_poisoned() {
+        let dispatcher = Bus::<Event>::new();
+        let _rx = dispatcher.add_rx();
+
+        let inner = dispatcher.inner.clone();
+        let _ = thread::spawn(move || {
+            let _guard = inner.write().unwrap();
+            panic!("Poison the lock");
+        })
+        .join();
+
+        assert_eq!(
+            dispatcher.try_broadcast(Event::Start),
+            Err(BroadcastError::Poisoned)
+        );
+    }
+
+    #[test]
+    fn subscribe_distinct() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let _sub = dispatcher.subscribe_distinct(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+
+        for i in &[1, 1, 2, 2, 2, 3] {
+            dispatcher.broadcast(*i);
+        }
+
+        for expected in &[1, 2, 3] {
+            match rx_test.recv_timeout(Duration::from_millis(100)) {
+                Err(_) => panic!("Event not received"),
+                Ok(e) => assert_eq!(e, *expected),
+            }
+        }
+
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn subscribe_batched() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<Vec<i32>>();
+
+        let sub = dispatcher.subscribe_batched(
+            4,
+            Box::new(move |batch| {
+                tx_test.send(batch).unwrap();
+            }),
+        );
+
+        for i in 0..10 {
+            dispatcher.broadcast(i);
+        }
+
+        sub.dispose_draining();
+
+        let batches = rx_test.iter().collect::<Vec<_>>();
+        assert!(batches
+            .iter()
+            .all(|batch| !batch.is_empty() && batch.len() <= 4));
+        assert_eq!(batches.concat(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn subscribe_until() {
+        let dispatcher = Bus::<i32>::new();
+        let rx = dispatcher.add_rx();
+
+        for i in 0..5 {
+            dispatcher.broadcast(i);
+        }
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_cb = seen.clone();
+        let seen_stop = seen.clone();
+
+        // The bus is still alive so this would block forever without should_stop
+        rx.subscribe_until(
+            Box::new(move |event| seen_cb.lock().unwrap().push(event)),
+            Box::new(move || seen_stop.lock().unwrap().len() == 3),
+        );
+
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2]);
+        assert_eq!(rx.try_recv(), Ok(3));
+    }
+
+    #[test]
+    fn subscribe_with_unboxed_closures() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_test, rx_test) = unbounded::<Event>();
+        let tx_blocking = tx_test.clone();
+
+        let _sub = dispatcher.subscribe_on_thread_fn(move |event| {
+            tx_test.send(event).unwrap();
+        });
+
+        let rx = dispatcher.add_rx();
+        dispatcher.broadcast(Event::Start);
+        drop(dispatcher);
+
+        // Returns once the bus has been dropped
+        rx.subscribe_fn(move |event| {
+            tx_blocking.send(event).unwrap();
+        });
+
+        for _ in 0..2 {
+            match rx_test.recv_timeout(Duration::from_millis(100)) {
+                Err(_) => panic!("Event not received"),
+                Ok(e) => assert_eq!(e, Event::Start),
+            }
+        }
+    }
+
+    #[test]
+    fn subscribe_with_builder() {
+        let dispatcher = Bus::<Event>::new();
+        let (tx_test, rx_test) = unbounded::<Option<String>>();
+
+        let _sub = SubscriptionBuilder::new()
+            .name("Custom subscription".to_string())
+            .stack_size(256 * 1024)
+            .subscribe_on_thread(
+                &dispatcher.add_rx(),
+                Box::new(move |_| {
+                    let name = thread::current().name().map(str::to_string);
+                    tx_test.send(name).unwrap();
+                }),
+            );
+
+        dispatcher.broadcast(Event::Start);
+
+        match rx_test.recv_timeout(Duration::from_millis(100)) {
+            Err(_) => panic!("Event not received"),
+            Ok(name) => assert_eq!(name.as_deref(), Some("Custom subscription")),
+        }
+    }
+
+    #[test]
+    fn subscription_dies_when_callback_panics() {
+        let dispatcher = Bus::<Event>::new();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+            if let Event::Stop(_) = event {
+                panic!("Sentinel event");
+            }
+        }));
+
+        dispatcher.broadcast(Event::Start);
+        assert!(sub.is_alive());
+
+        dispatcher.broadcast(Event::Stop(vec![]));
+        for _ in 0..100 {
+            if !sub.is_alive() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(!sub.is_alive());
+        assert!(sub.join().is_err());
+    }
+
+    #[test]
+    fn subscribe_latest() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let rx = dispatcher.add_rx();
+        for i in 1..=3 {
+            dispatcher.broadcast(i);
+        }
+
+        // Every event is already buffered so they're coalesced into the last one
+        let _sub = rx.subscribe_latest(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+
+        assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(3));
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(20)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn dispose_subscription_group() {
+        let dispatcher = Bus::<Event>::new();
+        let sub1 = dispatcher.subscribe_on_thread(Box::new(|_| {}));
+        let sub2 = dispatcher.subscribe_on_thread(Box::new(|_| {}));
+
+        let mut group = SubscriptionGroup::new();
+        group.push(sub1.clone());
+        group.push(sub2.clone());
+        assert_eq!(group.len(), 2);
+
+        group.dispose();
+        for _ in 0..100 {
+            if !sub1.is_alive() && !sub2.is_alive() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert!(!sub1.is_alive());
+        assert!(!sub2.is_alive());
+    }
+
+    #[test]
+    fn subscribe_throttled() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let sub = dispatcher.subscribe_throttled(
+            Duration::from_secs(10),
+            Box::new(move |event| {
+                tx_test.send(event).unwrap();
+            }),
+        );
+
+        for i in 0..100 {
+            dispatcher.broadcast(i);
+        }
+        sub.dispose_draining();
+
+        // Only the first event arrives within the interval
+        assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(0));
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn subscribe_folding() {
+        let dispatcher = Bus::<i32>::new();
+
+        let sub = dispatcher.subscribe_folding(0, |total, event| *total += event);
+        for i in 1..=10 {
+            dispatcher.broadcast(i);
+        }
+
+        sub.dispose_draining();
+        assert_eq!(sub.into_state(), 55);
+    }
+
+    #[test]
+    fn subscribe_idle_timeout() {
+        let dispatcher = Bus::<i32>::new();
+        let rx = dispatcher.add_rx();
+        dispatcher.broadcast(1);
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let seen_cb = seen.clone();
+
+        // The bus is still alive so this would block forever without the timeout
+        let start = Instant::now();
+        rx.subscribe_idle_timeout(
+            Duration::from_millis(50),
+            Box::new(move |event| seen_cb.lock().unwrap().push(event)),
+        );
+
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert_eq!(*seen.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn subscribe_replaceable() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_old, rx_old) = unbounded::<i32>();
+        let (tx_new, rx_new) = unbounded::<i32>();
+
+        let sub = dispatcher.subscribe_replaceable(Box::new(move |event| {
+            tx_old.send(event).unwrap();
+        }));
+
+        dispatcher.broadcast(1);
+        assert_eq!(rx_old.recv_timeout(Duration::from_millis(100)), Ok(1));
+
+        sub.set_callback(Box::new(move |event| {
+            tx_new.send(event).unwrap();
+        }));
+
+        dispatcher.broadcast(2);
+        assert_eq!(rx_new.recv_timeout(Duration::from_millis(100)), Ok(2));
+        // The old callback has been dropped
+        assert_eq!(rx_old.try_recv(), Err(TryRecvError::Disconnected));
+    }
+
+    #[test]
+    fn flush_subscription() {
+        let dispatcher = Bus::<i32>::new();
+        let count = Arc::new(Mutex::new(0));
+        let count_cb = count.clone();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |_| {
+            *count_cb.lock().unwrap() += 1;
+        }));
+
+        for i in 0..1_000 {
+            dispatcher.broadcast(i);
+        }
+
+        sub.flush();
+        assert_eq!(*count.lock().unwrap(), 1_000);
+
+        // The subscription keeps running after a flush
+        dispatcher.broadcast(0);
+        sub.flush();
+        assert_eq!(*count.lock().unwrap(), 1_001);
+    }
+
+    #[test]
+    fn ids_wrap_around_without_collisions() {
+        let bus = Bus::<i32>::new();
+        let (_rx0, handle) = bus.add_rx_with_handle();
+        assert_eq!(handle.id, 0);
+
+        bus.inner.write().unwrap().core.next_id = usize::MAX - 1;
+
+        let receivers = (0..3).map(|_| bus.add_rx_with_handle()).collect::<Vec<_>>();
+        let ids = receivers
+            .iter()
+            .map(|(_, handle)| handle.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![usize::MAX - 1, usize::MAX, 1]);
+        assert_eq!(bus.subscriber_ids(), vec![0, 1, usize::MAX - 1, usize::MAX]);
+    }
+
+    #[test]
+    fn detach() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+        sub.detach();
+
+        dispatcher.broadcast(1);
+        assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(1));
+
+        // The thread exits once the bus has been dropped
+        drop(dispatcher);
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn worker_modes() {
+        let modes = [
+            WorkerMode::Park,
+            WorkerMode::Spin,
+            WorkerMode::SpinThenPark { spins: 100 },
+        ];
+
+        for mode in modes {
+            let dispatcher = Bus::<i32>::new();
+            let (tx_test, rx_test) = unbounded::<i32>();
+
+            let sub = SubscriptionBuilder::new()
+                .worker_mode(mode)
+                .subscribe_on_thread(
+                    &dispatcher.add_rx(),
+                    Box::new(move |event| {
+                        tx_test.send(event).unwrap();
+                    }),
+                );
+
+            dispatcher.broadcast(1);
+            dispatcher.broadcast(2);
+            assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(1));
+            assert_eq!(rx_test.recv_timeout(Duration::from_millis(100)), Ok(2));
+
+            sub.join().unwrap();
+            assert_eq!(rx_test.try_recv(), Err(TryRecvError::Disconnected));
+        }
+    }
+
+    #[test]
+    fn subscribe_coalesced() {
+        let dispatcher = Bus::<(char, i32)>::new();
+        let rx = dispatcher.add_rx();
+        let (tx_test, rx_test) = unbounded::<(char, i32)>();
+
+        // Broadcast before subscribing so every update is buffered in one batch
+        dispatcher.broadcast(('A', 1));
+        dispatcher.broadcast(('B', 1));
+        dispatcher.broadcast(('A', 2));
+        dispatcher.broadcast(('B', 2));
+
+        let _sub = rx.subscribe_coalesced(
+            |(key, _)| *key,
+            Box::new(move |event| {
+                tx_test.send(event).unwrap();
+            }),
+        );
+
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Ok(('A', 2))
+        );
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(100)),
+            Ok(('B', 2))
+        );
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(50)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn unique_thread_names() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<String>();
+
+        let subs = (0..2)
+            .map(|_| {
+                let tx_test = tx_test.clone();
+                dispatcher.subscribe_on_thread(Box::new(move |_| {
+                    let name = thread::current().name().unwrap().to_string();
+                    tx_test.send(name).unwrap();
+                }))
+            })
+            .collect::<Vec<_>>();
+
+        dispatcher.broadcast(1);
+        let first = rx_test.recv_timeout(Duration::from_millis(100)).unwrap();
+        let second = rx_test.recv_timeout(Duration::from_millis(100)).unwrap();
+        assert!(first.starts_with("double_decker-sub-"));
+        assert!(second.starts_with("double_decker-sub-"));
+        assert_ne!(first, second);
+
+        drop(subs);
+    }
+
+    #[test]
+    fn subscription_handle() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<i32>();
+        let rx = dispatcher.add_rx();
+
+        let sub = dispatcher.subscribe_on_thread(Box::new(move |event| {
+            tx_test.send(event).unwrap();
+        }));
+        let handle = sub.handle().unwrap();
+        assert_eq!(sub.id(), Some(1));
+        assert_eq!(dispatcher.subscriber_ids(), vec![0, 1]);
+
+        assert_eq!(dispatcher.broadcast_except(1, handle), 1);
+        assert_eq!(rx.try_recv(), Ok(1));
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(50)),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        // Subscriptions to a plain `Receiver` have no handle
+        let plain = rx.subscribe_on_thread(Box::new(|_| {}));
+        assert!(plain.handle().is_none());
+        assert_eq!(plain.id(), None);
+    }
+
+    #[test]
+    fn subscribe_windowed() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<Vec<i32>>();
+
+        let _sub = dispatcher.subscribe_windowed(
+            Duration::from_millis(100),
+            true,
+            Box::new(move |batch| {
+                tx_test.send(batch).unwrap();
+            }),
+        );
+
+        for i in 0..3 {
+            dispatcher.broadcast(i);
+        }
+
+        // Nothing is delivered until the window elapses
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(20)),
+            Err(RecvTimeoutError::Timeout)
+        );
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_secs(1)),
+            Ok(vec![0, 1, 2])
+        );
+
+        // Empty windows are skipped
+        assert_eq!(
+            rx_test.recv_timeout(Duration::from_millis(250)),
+            Err(RecvTimeoutError::Timeout)
+        );
+    }
+
+    #[test]
+    fn subscribe_windowed_empty() {
+        let dispatcher = Bus::<i32>::new();
+        let (tx_test, rx_test) = unbounded::<Vec<i32>>();
+
+        let _sub = dispatcher.subscribe_windowed(
+            Duration::from_millis(20),
+            false,
+            Box::new(move |batch| {
+                tx_test.send(batch).unwrap();
+            }),
+        );
+
+        assert_eq!(rx_test.recv_timeout(Duration::from_secs(1)), Ok(vec![]));
+    }
+
+    #[test]
+    fn wait_for_subscribers_removes_its_observer() {
+        let bus = Bus::<i32>::new();
+        let _rx = bus.add_rx();
+
+        for _ in 0..3 {
+            assert!(bus.wait_for_subscribers(1, Duration::from_millis(10)));
+            assert!(!bus.wait_for_subscribers(2, Duration::from_millis(10)));
+        }
+        assert!(bus.inner.read().unwrap().lifecycle.is_empty());
+    }
+
     #[test]
     fn clone_subscription_without_dropping() {
         let dispatcher = Bus::<Event>::new();
@@ -0,0 +1,75 @@
+use std::time::{Duration, Instant};
+
+use crossbeam::channel::{unbounded, Receiver};
+
+use crate::{Bus, BusInner};
+
+/// A change to the `Receiver`s registered with a `Bus`
+///
+/// Sent to the `Receiver`s returned by `Bus::lifecycle()`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// A `Receiver` with this id was added
+    Added(usize),
+    /// The `Receiver` with this id was removed or pruned after it was dropped
+    Removed(usize),
+}
+
+impl<T: Clone> BusInner<T> {
+    pub(crate) fn notify_lifecycle(&mut self, event: LifecycleEvent) {
+        // Observers that have been dropped are forgotten
+        self.lifecycle
+            .retain(|observer| observer.send(event).is_ok());
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Returns a `Receiver` that is notified whenever a `Receiver` is added to or removed from
+    /// the bus
+    ///
+    /// Disconnected `Receiver`s are only reported as removed once a broadcast prunes them. Each
+    /// call returns a new `Receiver` that only sees changes made after it was created.
+    pub fn lifecycle(&self) -> Receiver<LifecycleEvent> {
+        let (sender, receiver) = unbounded();
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .lifecycle
+            .push(sender);
+        receiver
+    }
+
+    /// Blocks until at least `n` `Receiver`s are registered or `timeout` elapses
+    ///
+    /// Returns whether `n` `Receiver`s were registered. This is useful in tests where
+    /// `Receiver`s are added from other threads so events aren't broadcast before anyone is
+    /// listening. See `subscriber_count()` for caveats about disconnected `Receiver`s.
+    pub fn wait_for_subscribers(&self, n: usize, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        // Observe before checking the count so no additions are missed in between
+        let (observer, changes) = unbounded();
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .lifecycle
+            .push(observer.clone());
+
+        let mut reached = true;
+        while self.subscriber_count() < n {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if changes.recv_timeout(remaining).is_err() {
+                reached = self.subscriber_count() >= n;
+                break;
+            }
+        }
+
+        // Otherwise the observer would only be forgotten after the next change
+        self.inner
+            .write()
+            .expect("Lock was poisoned")
+            .lifecycle
+            .retain(|other| !other.same_channel(&observer));
+
+        reached
+    }
+}
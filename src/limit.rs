@@ -0,0 +1,74 @@
+use std::sync::{Arc, RwLock};
+
+use crate::{Bus, BusInner, Subscriber};
+
+impl<T: Clone> BusInner<T> {
+    /// Returns the `Sender`s the next event should be sent to
+    ///
+    /// Every broadcast sends through this so the global limit applies however the event is
+    /// broadcast.
+    pub(crate) fn fan_out(&self) -> impl Iterator<Item = &Subscriber<T>> {
+        let slowest = self.slowest_over_limit();
+        self.core
+            .senders
+            .iter()
+            .filter(move |subscriber| under_limit(slowest, subscriber))
+    }
+
+    /// Returns the number of buffered events at or above which a `Sender` is skipped, if the
+    /// global limit has been reached
+    pub(crate) fn slowest_over_limit(&self) -> Option<usize> {
+        let limit = self.global_limit?;
+
+        let total: usize = self
+            .core
+            .senders
+            .iter()
+            .map(|subscriber| subscriber.sender.len())
+            .sum();
+        if total < limit {
+            return None;
+        }
+
+        self.core
+            .senders
+            .iter()
+            .map(|subscriber| subscriber.sender.len())
+            .max()
+    }
+}
+
+/// Returns `false` if `subscriber` is one of the slowest once the global limit has been reached
+///
+/// Every `Sender` tied for the most buffered events is skipped, not just one of them.
+pub(crate) fn under_limit<T>(slowest: Option<usize>, subscriber: &Subscriber<T>) -> bool {
+    match slowest {
+        Some(slowest) => subscriber.sender.len() < slowest,
+        None => true,
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Creates a new `double_decker::Bus` that limits the total number of events buffered across
+    /// all `Receiver`s
+    ///
+    /// Once `max_total` events are buffered, every broadcast skips the `Receiver`s with the most
+    /// buffered events so the slowest consumers miss out rather than memory growing without
+    /// bound. If several `Receiver`s are tied for the most buffered events, they all miss out
+    /// rather than one being picked arbitrarily. The total is approximate as consumers drain
+    /// their `Receiver`s concurrently and every `Receiver` that isn't skipped still gets the
+    /// event, so the total can exceed `max_total` by up to the number of `Receiver`s.
+    ///
+    /// Every broadcast adds up the events buffered in each `Receiver` to check the limit, so
+    /// broadcasting costs O(n) in the number of `Receiver`s even before any are sent the event.
+    pub fn with_global_limit(max_total: usize) -> Self {
+        let inner = BusInner {
+            global_limit: Some(max_total),
+            ..Default::default()
+        };
+
+        Bus {
+            inner: Arc::new(RwLock::new(inner)),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crossbeam::channel::TrySendError;
+
+use crate::{clone_until_last, Bus};
+
+/// What happened to each `Receiver` during `Bus::broadcast_lossy()`
+///
+/// Formats as a one line summary of the counts for logging, eg.
+/// `delivered=3 dropped_full=1 disconnected=0`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BroadcastReport {
+    /// The number of `Receiver`s the event was delivered to
+    pub delivered: usize,
+    /// The ids of bounded `Receiver`s that were full so missed out on the event
+    pub dropped_full: Vec<usize>,
+    /// The ids of `Receiver`s that had been dropped and were removed from the bus
+    pub disconnected: Vec<usize>,
+}
+
+impl fmt::Display for BroadcastReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "delivered={} dropped_full={} disconnected={}",
+            self.delivered,
+            self.dropped_full.len(),
+            self.disconnected.len()
+        )
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Broadcast to all `Receiver`s and report which ones missed out on the event
+    ///
+    /// Like `broadcast()`, this never blocks. Bounded `Receiver`s that are full are left on the
+    /// bus and only `Receiver`s that have been dropped are removed.
+    pub fn broadcast_lossy(&self, event: T) -> BroadcastReport {
+        let mut dropped_full = Vec::with_capacity(0);
+        let mut disconnected = Vec::with_capacity(0);
+
+        let delivered = self.broadcast_with(|inner| {
+            inner.record(&event);
+
+            let mut delivered = 0;
+            let mut next_event = clone_until_last(event);
+            let mut senders = inner.fan_out().peekable();
+
+            while let Some(subscriber) = senders.next() {
+                match subscriber
+                    .sender
+                    .try_send(next_event(senders.peek().is_none()))
+                {
+                    Ok(()) => delivered += 1,
+                    Err(TrySendError::Full(_)) => dropped_full.push(subscriber.id),
+                    Err(TrySendError::Disconnected(_)) => disconnected.push(subscriber.id),
+                }
+            }
+
+            inner.stats.record_broadcast(delivered);
+            (delivered, disconnected.clone())
+        });
+
+        BroadcastReport {
+            delivered,
+            dropped_full,
+            disconnected,
+        }
+    }
+
+    /// Broadcast to all `Receiver`s and return the events that couldn't be delivered because
+    /// their `Receiver` had been dropped
+    ///
+    /// Returns the number of `Receiver`s the event was delivered to along with the id of each
+    /// dropped `Receiver` and the event recovered from its failed send, so lost events can be
+    /// logged or sent elsewhere. The dropped `Receiver`s are removed as with `broadcast()`.
+    /// Bounded `Receiver`s that are full aren't included.
+    pub fn broadcast_capture(&self, event: T) -> (usize, Vec<(usize, T)>) {
+        let mut failed = Vec::with_capacity(0);
+
+        let delivered = self.broadcast_with(|inner| {
+            inner.record(&event);
+
+            let mut delivered = 0;
+            let mut next_event = clone_until_last(event);
+            let mut senders = inner.fan_out().peekable();
+
+            while let Some(subscriber) = senders.next() {
+                match subscriber
+                    .sender
+                    .try_send(next_event(senders.peek().is_none()))
+                {
+                    Ok(()) => delivered += 1,
+                    Err(TrySendError::Full(_)) => {}
+                    Err(TrySendError::Disconnected(event)) => failed.push((subscriber.id, event)),
+                }
+            }
+
+            inner.stats.record_broadcast(delivered);
+            (delivered, failed.iter().map(|(id, _)| *id).collect())
+        });
+
+        (delivered, failed)
+    }
+}
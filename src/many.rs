@@ -0,0 +1,61 @@
+use crossbeam::channel::Select;
+
+use crate::{BoxedFn, Bus, Signal, Subscription, SubscriptionBuilder};
+
+/// Calls `callback` on every broadcast from any of `buses` from a single thread
+///
+/// A `Receiver` is added to each bus and the thread waits for an event from any of them. Events
+/// from different buses are passed to `callback` in the order they are received. The thread
+/// exits when the `Subscription` is dropped or once every bus has been dropped.
+#[must_use]
+pub fn subscribe_many<T>(buses: &[&Bus<T>], mut callback: BoxedFn<T>) -> Subscription
+where
+    T: Clone + Send + 'static,
+{
+    let receivers = buses.iter().map(|bus| bus.add_rx()).collect::<Vec<_>>();
+
+    SubscriptionBuilder::new().spawn_with(
+        "Receiver many subscription thread",
+        move |terminate_rx| {
+            let mut select = Select::new();
+            for receiver in &receivers {
+                select.recv(receiver);
+            }
+            let terminate = select.recv(&terminate_rx);
+
+            let mut connected = receivers.len();
+            while connected > 0 {
+                let operation = select.select();
+                let index = operation.index();
+
+                if index == terminate {
+                    let signal = operation.recv(&terminate_rx);
+                    if let Ok(Signal::Flush(_) | Signal::TerminateDraining) = signal {
+                        for receiver in &receivers {
+                            for event in receiver.try_iter() {
+                                callback(event);
+                            }
+                        }
+                    }
+
+                    match signal {
+                        Ok(Signal::Flush(ack)) => {
+                            let _ = ack.send(());
+                            continue;
+                        }
+                        _ => return,
+                    }
+                }
+
+                match operation.recv(&receivers[index]) {
+                    Ok(event) => callback(event),
+                    // This bus has been dropped so stop waiting on it
+                    Err(_) => {
+                        select.remove(index);
+                        connected -= 1;
+                    }
+                }
+            }
+        },
+    )
+}
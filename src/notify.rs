@@ -0,0 +1,20 @@
+use crate::Bus;
+
+impl<T: Clone + Default> Bus<T> {
+    /// Broadcast `T::default()` to all `Receiver`s
+    ///
+    /// Useful for buses that carry "tick" or "reset" style events. Returns the number of
+    /// `Receiver`s the event was delivered to.
+    pub fn broadcast_default(&self) -> usize {
+        self.broadcast(T::default())
+    }
+}
+
+impl Bus<()> {
+    /// Wakes every `Receiver` of a notification bus
+    ///
+    /// Same as `broadcast(())`. Returns the number of `Receiver`s that were notified.
+    pub fn notify(&self) -> usize {
+        self.broadcast(())
+    }
+}
@@ -0,0 +1,53 @@
+use crossbeam::channel::TrySendError;
+use rayon::prelude::*;
+
+use crate::{limit::under_limit, Bus, BusInner};
+
+impl<T: Clone + Send + Sync> BusInner<T> {
+    pub fn broadcast_parallel(&self, event: T) -> (usize, Vec<usize>) {
+        self.record(&event);
+
+        let slowest = self.slowest_over_limit();
+
+        let (delivered, disconnected) = self
+            .core
+            .senders
+            .par_iter()
+            .filter(|subscriber| under_limit(slowest, subscriber))
+            .fold(
+                || (0, Vec::new()),
+                |(delivered, mut disconnected), subscriber| {
+                    match subscriber.sender.try_send(event.clone()) {
+                        Ok(()) => return (delivered + 1, disconnected),
+                        // Bounded receiver is full so it misses out on this event
+                        Err(TrySendError::Full(_)) => {}
+                        Err(TrySendError::Disconnected(_)) => disconnected.push(subscriber.id),
+                    }
+                    (delivered, disconnected)
+                },
+            )
+            .reduce(
+                || (0, Vec::new()),
+                |(a, mut a_disconnected), (b, b_disconnected)| {
+                    a_disconnected.extend(b_disconnected);
+                    (a + b, a_disconnected)
+                },
+            );
+
+        self.stats.record_broadcast(delivered);
+        (delivered, disconnected)
+    }
+}
+
+impl<T: Clone + Send + Sync> Bus<T> {
+    /// Broadcast to all `Receiver`s in parallel using rayon
+    ///
+    /// This can be faster than `broadcast()` when there are many `Receiver`s and `T` is expensive
+    /// to clone. Each `Receiver` still gets the event exactly once but the order in which
+    /// `Receiver`s are sent the event is no longer deterministic. Every `Receiver` gets a clone so
+    /// the original event is dropped. Returns the number of `Receiver`s the event was delivered
+    /// to.
+    pub fn broadcast_parallel(&self, event: T) -> usize {
+        self.broadcast_with(|inner| inner.broadcast_parallel(event))
+    }
+}
@@ -0,0 +1,32 @@
+use crossbeam::channel::Receiver;
+
+use crate::Bus;
+
+/// A subscription that is driven by calling `poll()` from your own loop
+///
+/// This suits single threaded event loops where neither a blocking `subscribe()` nor a
+/// subscription thread is wanted.
+pub struct PollingSubscription<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T: Clone> PollingSubscription<T> {
+    /// Adds a new `Receiver` to `bus` to poll
+    pub fn new(bus: &Bus<T>) -> Self {
+        PollingSubscription {
+            receiver: bus.add_rx(),
+        }
+    }
+
+    /// Passes every event currently buffered to `callback` without blocking
+    ///
+    /// Returns the number of events passed to `callback`.
+    pub fn poll(&mut self, callback: &mut dyn FnMut(T)) -> usize {
+        let mut count = 0;
+        for event in self.receiver.try_iter() {
+            callback(event);
+            count += 1;
+        }
+        count
+    }
+}
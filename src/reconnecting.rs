@@ -0,0 +1,63 @@
+use std::sync::{Arc, RwLock, Weak};
+
+use crossbeam::channel::{Receiver, RecvError, TryRecvError};
+
+use crate::{Bus, BusInner};
+
+/// A `Receiver` that adds itself back to the bus if it's removed
+///
+/// Obtained from `Bus::add_rx_reconnecting()`. Once its `Receiver` is disconnected, for example
+/// by `Bus::clear()` or `Bus::remove_rx()`, a new `Receiver` is added to the bus. This only holds
+/// a weak reference so it doesn't keep the bus alive. Once every handle to the bus has been
+/// dropped it reports that it's disconnected like a normal `Receiver`. Events broadcast between
+/// the disconnection and the new `Receiver` being added are missed.
+pub struct ReconnectingReceiver<T: Clone> {
+    bus: Weak<RwLock<BusInner<T>>>,
+    receiver: Receiver<T>,
+}
+
+impl<T: Clone> ReconnectingReceiver<T> {
+    /// Blocks until an event is received
+    ///
+    /// Returns an error once the bus has been dropped.
+    pub fn recv(&mut self) -> Result<T, RecvError> {
+        loop {
+            match self.receiver.recv() {
+                Ok(event) => return Ok(event),
+                Err(RecvError) => self.reconnect().ok_or(RecvError)?,
+            }
+        }
+    }
+
+    /// Returns an event if one is buffered without blocking
+    ///
+    /// If the `Receiver` was disconnected while the bus still exists, a new `Receiver` is added
+    /// and this returns `TryRecvError::Empty`.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        match self.receiver.try_recv() {
+            Err(TryRecvError::Disconnected) => {
+                self.reconnect().ok_or(TryRecvError::Disconnected)?;
+                Err(TryRecvError::Empty)
+            }
+            result => result,
+        }
+    }
+
+    fn reconnect(&mut self) -> Option<()> {
+        let inner = self.bus.upgrade()?;
+        self.receiver = Bus { inner }.add_rx();
+        Some(())
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Adds a `ReconnectingReceiver` to the bus
+    ///
+    /// See `ReconnectingReceiver` for details.
+    pub fn add_rx_reconnecting(&self) -> ReconnectingReceiver<T> {
+        ReconnectingReceiver {
+            bus: Arc::downgrade(&self.inner),
+            receiver: self.add_rx(),
+        }
+    }
+}
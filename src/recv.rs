@@ -0,0 +1,31 @@
+use crossbeam::channel::Receiver;
+
+/// Helpers for taking buffered events from a `Receiver` without a subscription thread
+pub trait RecvExt<T> {
+    /// Returns every event currently buffered without blocking
+    fn drain(&self) -> Vec<T>;
+
+    /// Appends every event currently buffered to `buf` without blocking
+    ///
+    /// This lets a buffer be reused between calls rather than allocating a new `Vec`.
+    fn drain_into(&self, buf: &mut Vec<T>);
+
+    /// Discards up to `n` buffered events without blocking
+    ///
+    /// Returns the number of events discarded, which is less than `n` if fewer were buffered.
+    fn skip(&self, n: usize) -> usize;
+}
+
+impl<T> RecvExt<T> for Receiver<T> {
+    fn drain(&self) -> Vec<T> {
+        self.try_iter().collect()
+    }
+
+    fn drain_into(&self, buf: &mut Vec<T>) {
+        buf.extend(self.try_iter());
+    }
+
+    fn skip(&self, n: usize) -> usize {
+        self.try_iter().take(n).count()
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::{Arc, Mutex};
+
+use crate::{BoxedFn, Subscription};
+
+/// A `Subscription` whose callback can be swapped while it's running
+///
+/// Returned by `SubscribeToReader::subscribe_replaceable()`.
+pub struct ReplaceableSubscription<T> {
+    subscription: Subscription,
+    callback: Arc<Mutex<BoxedFn<T>>>,
+}
+
+impl<T> ReplaceableSubscription<T> {
+    pub(crate) fn new(subscription: Subscription, callback: Arc<Mutex<BoxedFn<T>>>) -> Self {
+        ReplaceableSubscription {
+            subscription,
+            callback,
+        }
+    }
+
+    /// Replaces the callback
+    ///
+    /// This waits for the current callback to return if it's running. Events that are still
+    /// buffered are passed to the new callback, so nothing is lost by swapping.
+    pub fn set_callback(&self, callback: BoxedFn<T>) {
+        *self.callback.lock().expect("Lock was poisoned") = callback;
+    }
+
+    /// See `Subscription::is_alive()`
+    pub fn is_alive(&self) -> bool {
+        self.subscription.is_alive()
+    }
+
+    /// See `Subscription::dispose_draining()`
+    pub fn dispose_draining(&self) {
+        self.subscription.dispose_draining();
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::atomic::Ordering;
+
+use crate::{Bus, SubscribeToReader, Subscription};
+
+impl<T: Clone + Send + 'static> Bus<(u64, T)> {
+    /// Broadcast `event` to all `Receiver`s along with the next sequence number
+    ///
+    /// Sequence numbers start at zero and increase by one for every call so subscribers can
+    /// detect duplicate or missed events without `T` implementing `PartialEq`. Every `Receiver`
+    /// gets the same sequence number for an event. Concurrent calls may deliver events out of
+    /// sequence order, see `broadcast_ordered()`. Returns the sequence number assigned or `None`
+    /// if the bus has been closed, in which case no sequence number is used up.
+    pub fn broadcast_seq(&self, event: T) -> Option<u64> {
+        let mut assigned = None;
+
+        self.broadcast_with(|inner| {
+            let seq = inner.seq.fetch_add(1, Ordering::Relaxed);
+            assigned = Some(seq);
+            inner.broadcast((seq, event))
+        });
+
+        assigned
+    }
+
+    /// Calls `callback` with the sequence number and event of every broadcast from another
+    /// thread
+    #[must_use]
+    pub fn subscribe_seq(&self, mut callback: Box<dyn FnMut(u64, T) + Send>) -> Subscription {
+        self.subscribe_on_thread(Box::new(move |(seq, event)| callback(seq, event)))
+    }
+}
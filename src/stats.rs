@@ -0,0 +1,93 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::Bus;
+
+/// Snapshot of the counters returned by `Bus::stats()`
+///
+/// Formats as a one line summary for logging, eg. `broadcasts=120 delivered=480 pruned=3`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BusStats {
+    /// Number of events broadcast
+    pub broadcasts: u64,
+    /// Number of events delivered, summed across every `Receiver`
+    pub delivered: u64,
+    /// Number of disconnected `Receiver`s removed by broadcasts
+    pub pruned: u64,
+}
+
+impl fmt::Display for BusStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "broadcasts={} delivered={} pruned={}",
+            self.broadcasts, self.delivered, self.pruned
+        )
+    }
+}
+
+/// Counters updated by the bus
+///
+/// These are only for observability so `Relaxed` ordering is enough and keeps broadcasts cheap.
+#[derive(Default)]
+pub(crate) struct Stats {
+    broadcasts: AtomicU64,
+    delivered: AtomicU64,
+    pruned: AtomicU64,
+}
+
+impl Stats {
+    pub fn record_broadcast(&self, delivered: usize) {
+        self.broadcasts.fetch_add(1, Ordering::Relaxed);
+        self.delivered
+            .fetch_add(delivered as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_pruned(&self, pruned: usize) {
+        self.pruned.fetch_add(pruned as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> BusStats {
+        BusStats {
+            broadcasts: self.broadcasts.load(Ordering::Relaxed),
+            delivered: self.delivered.load(Ordering::Relaxed),
+            pruned: self.pruned.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl<T: Clone> Bus<T> {
+    /// Returns a snapshot of the bus counters
+    ///
+    /// The counters are updated independently so a snapshot taken while other threads are
+    /// broadcasting may be slightly inconsistent.
+    pub fn stats(&self) -> BusStats {
+        self.inner
+            .read()
+            .expect("Lock was poisoned")
+            .stats
+            .snapshot()
+    }
+
+    /// Returns the id of each `Receiver` and the number of events buffered in it, sorted by id
+    ///
+    /// This is approximate as consumers may be taking events concurrently. It includes
+    /// `Receiver`s that have been dropped but not yet pruned, which report no buffered events.
+    pub fn queue_depths(&self) -> Vec<(usize, usize)> {
+        let mut depths = self
+            .inner
+            .read()
+            .expect("Lock was poisoned")
+            .core
+            .senders
+            .iter()
+            .map(|subscriber| (subscriber.id, subscriber.sender.len()))
+            .collect::<Vec<_>>();
+        // Subscribers are sorted by priority first
+        depths.sort_unstable();
+        depths
+    }
+}
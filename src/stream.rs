@@ -0,0 +1,193 @@
+use std::{
+    collections::VecDeque,
+    future, mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use crossbeam::channel::{bounded, select, Receiver, RecvError, Sender};
+use futures_channel::mpsc::{self, UnboundedReceiver};
+use futures_core::Stream;
+
+use crate::{next_thread_name, Bus};
+
+/// Crossbeam channels can't wake a task so a thread forwards events from `receiver` to
+/// `forward` instead
+///
+/// The thread exits when the bus is dropped, when `forward` returns `false` or as soon as the
+/// returned `Sender` is dropped, even if no more events are broadcast.
+fn spawn_forwarder<T, F>(receiver: Receiver<T>, mut forward: F) -> Sender<()>
+where
+    T: Send + 'static,
+    F: FnMut(T) -> bool + Send + 'static,
+{
+    let (stop_tx, stop_rx) = bounded::<()>(0);
+
+    thread::Builder::new()
+        .name(next_thread_name("sub"))
+        .spawn(move || loop {
+            select! {
+                recv(receiver) -> event => match event {
+                    Ok(event) => {
+                        if !forward(event) {
+                            return;
+                        }
+                    }
+                    Err(_) => return,
+                },
+                // Nothing is ever sent so this only fires once the `Sender` is dropped
+                recv(stop_rx) -> _ => return,
+            }
+        })
+        .expect("Could not start Receiver stream thread");
+
+    stop_tx
+}
+
+/// A `Stream` of events broadcast on a `Bus`
+///
+/// Created by `Bus::subscribe_stream()`. Events are forwarded from a crossbeam `Receiver` by a
+/// thread that exits when the stream or the bus is dropped.
+pub struct EventStream<T> {
+    inner: UnboundedReceiver<T>,
+    // Dropped with the stream to stop the forwarding thread
+    _stop: Sender<()>,
+}
+
+impl<T: Send + 'static> EventStream<T> {
+    fn new(receiver: Receiver<T>) -> Self {
+        let (tx, rx) = mpsc::unbounded::<T>();
+        let stop = spawn_forwarder(receiver, move |event| tx.unbounded_send(event).is_ok());
+
+        EventStream {
+            inner: rx,
+            _stop: stop,
+        }
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+struct Inbox<T> {
+    events: VecDeque<T>,
+    // Every task waiting in `AsyncReceiver::recv()`
+    wakers: Vec<Waker>,
+    disconnected: bool,
+}
+
+impl<T> Inbox<T> {
+    /// Takes the wakers so they can be woken once the lock is released
+    fn take_wakers(&mut self) -> Vec<Waker> {
+        mem::take(&mut self.wakers)
+    }
+}
+
+// Owned by the forwarding thread so the `AsyncReceiver` is disconnected once the thread exits
+struct InboxSender<T> {
+    inbox: Arc<Mutex<Inbox<T>>>,
+}
+
+impl<T> InboxSender<T> {
+    fn send(&self, event: T) {
+        let wakers = {
+            let mut inbox = self.inbox.lock().expect("Lock was poisoned");
+            inbox.events.push_back(event);
+            inbox.take_wakers()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl<T> Drop for InboxSender<T> {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut inbox = self.inbox.lock().expect("Lock was poisoned");
+            inbox.disconnected = true;
+            inbox.take_wakers()
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// A `Receiver` whose events can be awaited
+///
+/// Created by `Bus::add_rx_async()`. Like `EventStream`, a thread forwards events from a
+/// crossbeam `Receiver` so other subscribers stay on plain crossbeam channels. Events are
+/// buffered in a queue that wakes every task waiting in `recv()`, so one `AsyncReceiver` can be
+/// shared between tasks. The thread exits when the `AsyncReceiver` or the bus is dropped.
+pub struct AsyncReceiver<T> {
+    inbox: Arc<Mutex<Inbox<T>>>,
+    // Dropped with the receiver to stop the forwarding thread
+    _stop: Sender<()>,
+}
+
+impl<T: Send + 'static> AsyncReceiver<T> {
+    fn new(receiver: Receiver<T>) -> Self {
+        let inbox = Arc::new(Mutex::new(Inbox {
+            events: VecDeque::new(),
+            wakers: Vec::new(),
+            disconnected: false,
+        }));
+
+        let sender = InboxSender {
+            inbox: inbox.clone(),
+        };
+        let stop = spawn_forwarder(receiver, move |event| {
+            sender.send(event);
+            true
+        });
+
+        AsyncReceiver { inbox, _stop: stop }
+    }
+}
+
+impl<T> AsyncReceiver<T> {
+    /// Waits for the next event
+    ///
+    /// Returns an error once the bus has been dropped and every buffered event has been
+    /// received. If several tasks are waiting, each event is received by only one of them.
+    pub async fn recv(&self) -> Result<T, RecvError> {
+        future::poll_fn(|cx| {
+            let mut inbox = self.inbox.lock().expect("Lock was poisoned");
+
+            if let Some(event) = inbox.events.pop_front() {
+                return Poll::Ready(Ok(event));
+            }
+            if inbox.disconnected {
+                return Poll::Ready(Err(RecvError));
+            }
+
+            if !inbox.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                inbox.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl<T: Clone + Send + 'static> Bus<T> {
+    /// Adds a new `AsyncReceiver<T>` whose events can be awaited
+    ///
+    /// Like `subscribe_stream()`, dropping it disconnects the underlying `Receiver` so it's
+    /// pruned on the next `broadcast()`.
+    pub fn add_rx_async(&self) -> AsyncReceiver<T> {
+        AsyncReceiver::new(self.add_rx())
+    }
+
+    /// Subscribes to the bus with a `Stream` of events
+    ///
+    /// The stream ends when the bus is dropped. Dropping the stream disconnects the underlying
+    /// `Receiver` so it's pruned on the next `broadcast()`.
+    pub fn subscribe_stream(&self) -> EventStream<T> {
+        EventStream::new(self.add_rx())
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{atomic::AtomicBool, Arc},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use crossbeam::channel::bounded;
+use tokio::task::JoinHandle;
+
+use crate::{
+    run_subscription, run_until_exit, BoxedFn, Bus, Signal, Subscription, Worker, WorkerMode,
+};
+
+impl<T: Clone + Send + 'static> Bus<T> {
+    /// Like `subscribe_on_thread` but `callback` is called from a tokio blocking task rather
+    /// than a dedicated thread
+    ///
+    /// Crossbeam channels can't wake an async task, so the task waits for events on tokio's
+    /// blocking thread pool. Each subscription holds on to one of the pool's threads until it's
+    /// disposed. Once every thread in the pool is in use, 512 by default, new subscriptions and
+    /// every other `spawn_blocking()` call wait in the queue until a subscription ends. Dropping
+    /// the runtime also blocks until every subscription has been disposed, unless it's shut down
+    /// with `shutdown_timeout()`.
+    ///
+    /// Dropping or disposing of the `Subscription` ends the task and `Subscription::join()`
+    /// waits for it to finish. Panics if called outside of a tokio runtime.
+    #[must_use]
+    pub fn subscribe_on_task(&self, mut callback: BoxedFn<T>) -> Subscription {
+        let (receiver, handle) = self.add_rx_with_handle();
+        let (terminate_tx, terminate_rx) = bounded::<Signal>(0);
+
+        let alive = Arc::new(AtomicBool::new(true));
+        let task_alive = alive.clone();
+
+        // A panic in the callback is passed on through the `JoinHandle`
+        let task = tokio::task::spawn_blocking(move || {
+            run_until_exit(task_alive, move || {
+                run_subscription(receiver, terminate_rx, WorkerMode::Park, |event, _| {
+                    callback(event);
+                    true
+                })
+            })
+        });
+
+        Subscription::from_worker(terminate_tx, Worker::Task(task), alive).with_handle(handle)
+    }
+}
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Blocks the current thread until `task` has finished
+///
+/// `Subscription::join()` isn't async so this polls the `JoinHandle` without needing a runtime.
+pub(crate) fn wait_for(mut task: JoinHandle<()>) -> thread::Result<()> {
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match Pin::new(&mut task).poll(&mut cx) {
+            Poll::Ready(Ok(())) => return Ok(()),
+            Poll::Ready(Err(e)) if e.is_panic() => return Err(e.into_panic()),
+            // The runtime was shut down before the task finished
+            Poll::Ready(Err(_)) => return Ok(()),
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crossbeam::channel::Receiver;
+
+use crate::Bus;
+
+/// A `Bus` that keeps its own `Receiver`s so tests can inspect fan-out without threads
+///
+/// ```
+/// use double_decker::TestBus;
+///
+/// let mut bus = TestBus::new();
+/// bus.add_rx();
+/// bus.add_rx();
+///
+/// assert_eq!(bus.broadcast_collect("Hello"), vec!["Hello", "Hello"]);
+/// ```
+pub struct TestBus<T: Clone> {
+    bus: Bus<T>,
+    receivers: Vec<Receiver<T>>,
+}
+
+impl<T: Clone> TestBus<T> {
+    /// Creates a new `TestBus` with no `Receiver`s
+    pub fn new() -> Self {
+        TestBus {
+            bus: Bus::new(),
+            receivers: Vec::new(),
+        }
+    }
+
+    /// Adds a `Receiver` that is retained by the `TestBus`
+    pub fn add_rx(&mut self) {
+        self.receivers.push(self.bus.add_rx());
+    }
+
+    /// Returns the underlying `Bus` so it can be passed to the code under test
+    pub fn bus(&self) -> &Bus<T> {
+        &self.bus
+    }
+
+    /// Broadcasts `event` and returns everything the retained `Receiver`s have buffered
+    ///
+    /// Events are returned in the order the `Receiver`s were added. Any events broadcast on the
+    /// underlying `Bus` since the last call are included too.
+    pub fn broadcast_collect(&self, event: T) -> Vec<T> {
+        self.bus.broadcast(event);
+        self.receivers
+            .iter()
+            .flat_map(|receiver| receiver.try_iter())
+            .collect()
+    }
+}
+
+impl<T: Clone> Default for TestBus<T> {
+    fn default() -> Self {
+        TestBus::new()
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, RwLock},
+};
+
+use crossbeam::channel::Receiver;
+
+use crate::Bus;
+
+/// Routes events to subscribers by topic
+///
+/// Each topic has its own `Bus` which is created the first time a `Receiver` is added for that
+/// topic. Subscribers only receive events broadcast to the topic they subscribed to.
+#[derive(Clone)]
+pub struct TopicBus<K: Eq + Hash + Clone, T: Clone> {
+    topics: Arc<RwLock<HashMap<K, Bus<T>>>>,
+}
+
+impl<K: Eq + Hash + Clone, T: Clone> TopicBus<K, T> {
+    /// Creates a new `double_decker::TopicBus`
+    pub fn new() -> Self {
+        TopicBus {
+            topics: Default::default(),
+        }
+    }
+
+    /// Adds a new `Receiver<T>` for events broadcast to `topic`
+    pub fn add_rx(&self, topic: K) -> Receiver<T> {
+        self.topics
+            .write()
+            .expect("Lock was poisoned")
+            .entry(topic)
+            .or_default()
+            .add_rx()
+    }
+
+    /// Broadcast to all `Receiver`s of `topic`
+    ///
+    /// Returns the number of `Receiver`s the event was delivered to. Broadcasting to a topic that
+    /// has never had a `Receiver` is a no-op.
+    pub fn broadcast(&self, topic: K, event: T) -> usize {
+        let bus = {
+            self.topics
+                .read()
+                .expect("Lock was poisoned")
+                .get(&topic)
+                .cloned()
+        };
+
+        match bus {
+            Some(bus) => bus.broadcast(event),
+            None => 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, T: Clone> Default for TopicBus<K, T> {
+    fn default() -> Self {
+        TopicBus::new()
+    }
+}
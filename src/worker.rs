@@ -0,0 +1,65 @@
+use std::hint;
+
+use crossbeam::channel::{select, Receiver, RecvError, TryRecvError};
+
+use crate::Signal;
+
+/// How a subscription thread waits when there are no events
+///
+/// Set with `SubscriptionBuilder::worker_mode()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WorkerMode {
+    /// Block until there's an event, which uses no CPU while idle
+    #[default]
+    Park,
+    /// Keep checking for events, which keeps a CPU core busy but reacts quickest
+    Spin,
+    /// Check for events `spins` times before blocking
+    SpinThenPark { spins: u32 },
+}
+
+/// The next thing a subscription thread has to handle
+pub(crate) enum Next<T> {
+    Event(Result<T, RecvError>),
+    Signal(Result<Signal, RecvError>),
+}
+
+impl WorkerMode {
+    /// Waits for the next event from `receiver` or signal from `terminate_rx`
+    pub(crate) fn next<T>(
+        self,
+        receiver: &Receiver<T>,
+        terminate_rx: &Receiver<Signal>,
+    ) -> Next<T> {
+        let spins = match self {
+            WorkerMode::Park => Some(0),
+            WorkerMode::Spin => None,
+            WorkerMode::SpinThenPark { spins } => Some(spins),
+        };
+
+        // `Spin` has no limit so never stops spinning
+        let mut spun = 0;
+        while spins != Some(spun) {
+            match terminate_rx.try_recv() {
+                Ok(signal) => return Next::Signal(Ok(signal)),
+                Err(TryRecvError::Disconnected) => return Next::Signal(Err(RecvError)),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            match receiver.try_recv() {
+                Ok(event) => return Next::Event(Ok(event)),
+                Err(TryRecvError::Disconnected) => return Next::Event(Err(RecvError)),
+                Err(TryRecvError::Empty) => {}
+            }
+
+            hint::spin_loop();
+            spun += 1;
+        }
+
+        // Blocks until there's an event or a signal so the thread doesn't spin when idle
+        select! {
+            recv(receiver) -> event => Next::Event(event),
+            recv(terminate_rx) -> signal => Next::Signal(signal),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+// Counts allocations made by the current thread so tests running in parallel don't interfere
+// with each other
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+#[test]
+fn broadcast_does_not_allocate() {
+    let bus = double_decker::Bus::<usize>::new();
+    // Bounded channels allocate their buffer up front whereas unbounded channels allocate blocks
+    // as they fill, so only bounded receivers let us observe the bus itself
+    let receivers = (0..1_000)
+        .map(|_| bus.add_rx_with_capacity(100))
+        .collect::<Vec<_>>();
+
+    let before = allocations();
+    for i in 0..100 {
+        assert_eq!(bus.broadcast(i), 1_000);
+    }
+    assert_eq!(allocations(), before);
+
+    for rx in receivers {
+        assert_eq!(rx.try_iter().count(), 100);
+    }
+}
+
+#[test]
+fn broadcast_without_subscribers_does_not_allocate() {
+    let bus = double_decker::Bus::<Vec<usize>>::new();
+
+    let before = allocations();
+    for _ in 0..100 {
+        assert_eq!(bus.broadcast(Vec::new()), 0);
+    }
+    assert_eq!(allocations(), before);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "async")]
+
+use futures::{executor::block_on, SinkExt, StreamExt};
+use std::time::{Duration, Instant};
+
+#[test]
+fn it_streams_events() {
+    let bus = double_decker::Bus::new();
+    let stream = bus.subscribe_stream();
+
+    for i in 0..10 {
+        bus.broadcast(i);
+    }
+    drop(bus);
+
+    let events = block_on(stream.collect::<Vec<_>>());
+    assert_eq!(events, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn it_prunes_dropped_streams() {
+    let bus = double_decker::Bus::new();
+    let mut stream = bus.subscribe_stream();
+
+    bus.broadcast(1);
+    assert_eq!(block_on(stream.next()), Some(1));
+    drop(stream);
+
+    // Dropping the stream stops the forwarding thread so the next broadcast reaps its receiver
+    let start = Instant::now();
+    while bus.subscriber_count() > 0 {
+        assert!(start.elapsed() < Duration::from_secs(1));
+        bus.broadcast(2);
+    }
+}
+
+#[test]
+fn it_stops_forwarding_without_another_broadcast() {
+    let bus = double_decker::Bus::new();
+    let rx = bus.add_rx_async();
+    drop(rx);
+
+    // The forwarding thread exits straight away so this broadcast isn't delivered
+    std::thread::sleep(Duration::from_millis(50));
+    assert_eq!(bus.broadcast(1), 0);
+    assert_eq!(bus.subscriber_count(), 0);
+}
+
+#[test]
+fn it_sinks_events() {
+    let bus = double_decker::Bus::new();
+    let rx = bus.add_rx();
+
+    block_on(futures::stream::iter(0..10).map(Ok).forward(bus.clone())).unwrap();
+    block_on(bus.clone().send(10)).unwrap();
+
+    assert_eq!(
+        rx.try_iter().collect::<Vec<_>>(),
+        (0..11).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_awaits_events() {
+    let bus = double_decker::Bus::new();
+    let rx = bus.add_rx_async();
+
+    bus.broadcast(1);
+    assert_eq!(block_on(rx.recv()), Ok(1));
+
+    let producer = bus.clone();
+    let j = std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        producer.broadcast(2);
+    });
+    assert_eq!(block_on(rx.recv()), Ok(2));
+    j.join().unwrap();
+
+    drop(bus);
+    assert_eq!(block_on(rx.recv()), Err(crossbeam::channel::RecvError));
+}
+
+#[test]
+fn it_shares_an_async_receiver_between_tasks() {
+    let bus = double_decker::Bus::new();
+    let rx = std::sync::Arc::new(bus.add_rx_async());
+
+    let waiters = (0..2)
+        .map(|_| {
+            let rx = rx.clone();
+            std::thread::spawn(move || block_on(rx.recv()))
+        })
+        .collect::<Vec<_>>();
+
+    // Both tasks are waiting before anything is broadcast
+    std::thread::sleep(Duration::from_millis(20));
+    bus.broadcast(1);
+    bus.broadcast(2);
+
+    let mut events = waiters
+        .into_iter()
+        .map(|waiter| waiter.join().unwrap().unwrap())
+        .collect::<Vec<_>>();
+    events.sort_unstable();
+    assert_eq!(events, vec![1, 2]);
+}
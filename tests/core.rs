@@ -0,0 +1,94 @@
+use double_decker::{BusCore, ChannelError, ChannelFactory, ChannelSender};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::{Rc, Weak},
+};
+
+// An in-memory channel that doesn't rely on crossbeam or threads
+type Queue = Rc<RefCell<VecDeque<u32>>>;
+
+struct MockSender {
+    queue: Weak<RefCell<VecDeque<u32>>>,
+    capacity: usize,
+}
+
+impl ChannelSender<u32> for MockSender {
+    fn try_send(&self, event: u32) -> Result<(), ChannelError<u32>> {
+        let queue = match self.queue.upgrade() {
+            Some(queue) => queue,
+            None => return Err(ChannelError::Disconnected(event)),
+        };
+
+        let mut queue = queue.borrow_mut();
+        if queue.len() == self.capacity {
+            return Err(ChannelError::Full(event));
+        }
+
+        queue.push_back(event);
+        Ok(())
+    }
+}
+
+struct MockChannels {
+    capacity: usize,
+}
+
+impl ChannelFactory<u32> for MockChannels {
+    type Sender = MockSender;
+    type Receiver = Queue;
+
+    fn channel(&mut self) -> (MockSender, Queue) {
+        let queue = Queue::default();
+        let sender = MockSender {
+            queue: Rc::downgrade(&queue),
+            capacity: self.capacity,
+        };
+        (sender, queue)
+    }
+}
+
+#[test]
+fn it_broadcasts_to_every_receiver() {
+    let mut core = BusCore::new(MockChannels { capacity: 10 });
+    let r1 = core.add_rx();
+    let r2 = core.add_rx();
+
+    assert_eq!(core.broadcast(1), 2);
+    assert_eq!(core.broadcast(2), 2);
+    assert_eq!(*r1.borrow(), [1, 2]);
+    assert_eq!(*r2.borrow(), [1, 2]);
+}
+
+#[test]
+fn it_skips_full_receivers() {
+    let mut core = BusCore::new(MockChannels { capacity: 1 });
+    let r1 = core.add_rx();
+
+    assert_eq!(core.broadcast(1), 1);
+    assert_eq!(core.broadcast(2), 0);
+    assert_eq!(*r1.borrow(), [1]);
+    assert_eq!(core.subscriber_count(), 1);
+}
+
+#[test]
+fn it_prunes_dropped_receivers() {
+    let mut core = BusCore::new(MockChannels { capacity: 10 });
+    let r1 = core.add_rx();
+    let r2 = core.add_rx();
+    drop(r2);
+
+    assert_eq!(core.broadcast(1), 1);
+    assert_eq!(core.subscriber_count(), 1);
+    assert_eq!(*r1.borrow(), [1]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn it_uses_crossbeam_channels() {
+    let mut core = BusCore::new(double_decker::CrossbeamChannels);
+    let r1 = core.add_rx();
+
+    assert_eq!(core.broadcast(1u32), 1);
+    assert_eq!(r1.try_recv(), Ok(1));
+}
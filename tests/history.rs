@@ -0,0 +1,58 @@
+#![cfg(feature = "history")]
+
+#[test]
+fn it_replays_history() {
+    let c = double_decker::Bus::with_history(3);
+    let live = c.add_rx();
+
+    for i in 0..5 {
+        c.broadcast(i);
+    }
+
+    // Only the last 3 events are retained
+    assert_eq!(c.history(), vec![2, 3, 4]);
+
+    let replay = c.add_rx_replay();
+    c.broadcast(5);
+
+    assert_eq!(replay.try_iter().collect::<Vec<_>>(), vec![2, 3, 4, 5]);
+    assert_eq!(live.try_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(c.history(), vec![3, 4, 5]);
+}
+
+#[test]
+fn it_has_no_history_by_default() {
+    let c = double_decker::Bus::new();
+    c.broadcast(1);
+    assert!(c.history().is_empty());
+
+    let replay = c.add_rx_replay();
+    c.broadcast(2);
+    assert_eq!(replay.try_iter().collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn it_skips_replayed_history() {
+    let c = double_decker::Bus::with_history(3);
+    for i in 0..3 {
+        c.broadcast(i);
+    }
+
+    let lagged = c.add_rx_lagged(2);
+    c.broadcast(3);
+    assert_eq!(lagged.try_iter().collect::<Vec<_>>(), vec![2, 3]);
+
+    // Skipping more than was retained only skips the replay
+    let lagged = c.add_rx_lagged(10);
+    c.broadcast(4);
+    assert_eq!(lagged.try_iter().collect::<Vec<_>>(), vec![4]);
+}
+
+#[test]
+fn it_builds_buses_with_history() {
+    let c = double_decker::BusBuilder::new().history(2).build();
+    for i in 0..3 {
+        c.broadcast(i);
+    }
+    assert_eq!(c.history(), vec![1, 2]);
+}
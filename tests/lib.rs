@@ -1,4 +1,10 @@
-use crossbeam::channel::TryRecvError;
+#![cfg(feature = "std")]
+
+use crossbeam::channel::{RecvError, TryRecvError};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
 
 // These tests were plagiarised from the bus crate!
 
@@ -138,3 +144,1203 @@ fn it_can_count_to_10000() {
     j.join().unwrap();
     assert_eq!(r1.try_recv(), Err(TryRecvError::Disconnected));
 }
+
+#[test]
+fn it_counts_subscribers() {
+    let c = double_decker::Bus::new();
+    assert!(c.is_empty());
+    assert_eq!(c.subscriber_count(), 0);
+
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+    assert!(!c.is_empty());
+    assert_eq!(c.subscriber_count(), 2);
+
+    // Dropped receivers are still counted until the next broadcast
+    drop(r2);
+    assert_eq!(c.subscriber_count(), 2);
+
+    c.broadcast(true);
+    assert_eq!(c.subscriber_count(), 1);
+    assert_eq!(r1.try_recv(), Ok(true));
+}
+
+#[test]
+fn it_returns_delivered_count() {
+    let c = double_decker::Bus::new();
+    assert_eq!(c.broadcast(true), 0);
+
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+    assert_eq!(c.broadcast(true), 2);
+
+    drop(r2);
+    // This broadcast reaps the disconnected sender
+    assert_eq!(c.broadcast(true), 1);
+    assert_eq!(c.subscriber_count(), 1);
+    assert_eq!(c.broadcast(true), 1);
+
+    assert_eq!(r1.try_iter().count(), 3);
+}
+
+#[test]
+fn it_drops_events_for_full_bounded_receivers() {
+    let c = double_decker::Bus::new();
+    let bounded = c.add_rx_with_capacity(2);
+    let unbounded = c.add_rx();
+
+    assert_eq!(c.broadcast(1), 2);
+    assert_eq!(c.broadcast(2), 2);
+    // The bounded receiver is now full
+    assert_eq!(c.broadcast(3), 1);
+
+    // Full receivers are not pruned
+    assert_eq!(c.subscriber_count(), 2);
+
+    assert_eq!(bounded.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(unbounded.try_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+
+    // Once drained, the bounded receiver gets events again
+    assert_eq!(c.broadcast(4), 2);
+    assert_eq!(bounded.try_recv(), Ok(4));
+    assert_eq!(unbounded.try_recv(), Ok(4));
+}
+
+#[test]
+fn it_removes_receivers_by_handle() {
+    let c = double_decker::Bus::new();
+    let (r1, h1) = c.add_rx_with_handle();
+    let r2 = c.add_rx();
+
+    c.remove_rx(h1);
+    assert_eq!(c.subscriber_count(), 1);
+    assert_eq!(r1.try_recv(), Err(TryRecvError::Disconnected));
+
+    // Removing twice is harmless
+    c.remove_rx(h1);
+    assert_eq!(c.subscriber_count(), 1);
+
+    assert_eq!(c.broadcast(true), 1);
+    assert_eq!(r2.try_recv(), Ok(true));
+}
+
+#[test]
+fn it_broadcasts_except_one() {
+    let c = double_decker::Bus::new();
+    let (r1, h1) = c.add_rx_with_handle();
+    let r2 = c.add_rx();
+    let r3 = c.add_rx();
+    drop(r3);
+
+    assert_eq!(c.broadcast_except(true, h1), 1);
+    // Disconnected receivers are still pruned
+    assert_eq!(c.subscriber_count(), 2);
+
+    assert_eq!(r1.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(r2.try_recv(), Ok(true));
+}
+
+#[test]
+fn it_debugs() {
+    #[derive(Clone)]
+    struct NotDebug;
+
+    let c = double_decker::Bus::<NotDebug>::new();
+    let _r1 = c.add_rx();
+    let _r2 = c.add_rx();
+    let _r3 = c.add_rx();
+
+    assert_eq!(format!("{:?}", c), "Bus { subscribers: 3, next_id: 3 }");
+}
+
+#[derive(Debug)]
+struct CountsClones(Arc<AtomicUsize>);
+
+impl Clone for CountsClones {
+    fn clone(&self) -> Self {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        CountsClones(self.0.clone())
+    }
+}
+
+#[test]
+fn it_only_clones_when_needed() {
+    let clones = Arc::new(AtomicUsize::new(0));
+    let c = double_decker::Bus::new();
+
+    // No subscribers means no clones
+    c.broadcast(CountsClones(clones.clone()));
+    c.broadcast_ref(&CountsClones(clones.clone()));
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+
+    let _r1 = c.add_rx();
+    let _r2 = c.add_rx();
+    let _r3 = c.add_rx();
+
+    // The last receiver gets the original
+    c.broadcast(CountsClones(clones.clone()));
+    assert_eq!(clones.load(Ordering::SeqCst), 2);
+
+    // Every receiver gets a clone and we keep the original
+    let event = CountsClones(clones.clone());
+    c.broadcast_ref(&event);
+    assert_eq!(clones.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn it_delivers_clones_when_the_last_receiver_is_dropped() {
+    let c = double_decker::Bus::new();
+    let live = c.add_rx();
+    let last = c.add_rx();
+    drop(last);
+
+    // The original is lost with the dropped receiver but the earlier receiver has its own clone
+    assert_eq!(c.broadcast(vec![1, 2, 3]), 1);
+    assert_eq!(live.try_recv(), Ok(vec![1, 2, 3]));
+
+    // Once pruned, the live receiver is last so gets the original
+    assert_eq!(c.broadcast(vec![4]), 1);
+    assert_eq!(live.try_recv(), Ok(vec![4]));
+}
+
+#[test]
+fn it_clears_receivers() {
+    let c = double_decker::Bus::new();
+    let receivers = (0..3).map(|_| c.add_rx()).collect::<Vec<_>>();
+    c.broadcast(true);
+
+    c.clear();
+    assert!(c.is_empty());
+    assert_eq!(c.broadcast(true), 0);
+
+    for rx in receivers {
+        assert_eq!(rx.recv(), Ok(true));
+        assert_eq!(rx.recv(), Err(RecvError));
+    }
+
+    // New receivers still work after clearing
+    let r = c.add_rx();
+    assert_eq!(c.broadcast(true), 1);
+    assert_eq!(r.try_recv(), Ok(true));
+}
+
+#[test]
+fn it_broadcasts_all() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+    drop(r2);
+
+    assert_eq!(c.broadcast_all(0..100), 100);
+    assert_eq!(c.subscriber_count(), 1);
+    assert_eq!(
+        r1.try_iter().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_reports_disconnections() {
+    let c = double_decker::Bus::new();
+    let disconnected = Arc::new(Mutex::new(Vec::new()));
+
+    let disconnected_cb = disconnected.clone();
+    c.on_disconnect(Box::new(move |id| disconnected_cb.lock().unwrap().push(id)));
+
+    let _r1 = c.add_rx();
+    let r2 = c.add_rx();
+    let (_r3, h3) = c.add_rx_with_handle();
+    drop(r2);
+
+    c.broadcast(true);
+    c.broadcast(true);
+    // Explicit removals are not reported
+    c.remove_rx(h3);
+
+    assert_eq!(*disconnected.lock().unwrap(), vec![1]);
+}
+
+#[test]
+fn it_routes_topics() {
+    let c = double_decker::TopicBus::new();
+    let a = c.add_rx("a");
+    let b = c.add_rx("b");
+
+    assert_eq!(c.broadcast("a", 1), 1);
+    assert_eq!(c.broadcast("b", 2), 1);
+    // Unknown topics are ignored
+    assert_eq!(c.broadcast("c", 3), 0);
+
+    assert_eq!(a.try_iter().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(b.try_iter().collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn it_works_with_capacity_hint() {
+    let c = double_decker::Bus::with_capacity_hint(100);
+    let receivers = (0..200).map(|_| c.add_rx()).collect::<Vec<_>>();
+
+    assert_eq!(c.subscriber_count(), 200);
+    assert_eq!(c.broadcast(true), 200);
+
+    for rx in receivers {
+        assert_eq!(rx.try_recv(), Ok(true));
+    }
+}
+
+#[test]
+fn it_errors_when_nobody_is_listening() {
+    let c = double_decker::Bus::new();
+    assert_eq!(
+        c.try_broadcast(true),
+        Err(double_decker::BroadcastError::NoSubscribers)
+    );
+
+    let r1 = c.add_rx();
+    assert_eq!(c.try_broadcast(true), Ok(1));
+    assert_eq!(r1.try_recv(), Ok(true));
+
+    drop(r1);
+    assert_eq!(
+        c.try_broadcast(true),
+        Err(double_decker::BroadcastError::NoSubscribers)
+    );
+    assert!(c.is_empty());
+}
+
+#[test]
+fn it_blocks_until_bounded_receivers_have_room() {
+    use std::{thread, time::Duration};
+
+    let c = double_decker::Bus::new();
+    let bounded = c.add_rx_with_capacity(1);
+    let unbounded = c.add_rx();
+
+    let j = thread::spawn(move || {
+        let mut received = Vec::new();
+        for event in bounded.iter() {
+            // Drain slowly so the producer has to wait
+            thread::sleep(Duration::from_millis(1));
+            received.push(event);
+        }
+        received
+    });
+
+    for i in 0..10 {
+        assert_eq!(c.broadcast_blocking(i), 2);
+    }
+    drop(c);
+
+    assert_eq!(j.join().unwrap(), (0..10).collect::<Vec<_>>());
+    assert_eq!(
+        unbounded.try_iter().collect::<Vec<_>>(),
+        (0..10).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_maps_buses() {
+    let c = double_decker::Bus::<i32>::new();
+    let mapped = c.map(|i| format!("#{}", i));
+    let rx = mapped.add_rx();
+
+    c.broadcast(1);
+    c.broadcast(2);
+    drop(c);
+
+    // The mapping thread exits when the source is dropped which disconnects the receiver
+    drop(mapped);
+    assert_eq!(
+        rx.iter().collect::<Vec<_>>(),
+        vec!["#1".to_string(), "#2".to_string()]
+    );
+}
+
+#[test]
+fn it_filter_maps_buses() {
+    let c = double_decker::Bus::<i32>::new();
+    let squares = c.filter_map(|i| if i % 2 == 0 { Some(i * i) } else { None });
+    let rx = squares.add_rx();
+
+    for i in 1..=5 {
+        c.broadcast(i);
+    }
+    drop(c);
+
+    drop(squares);
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec![4, 16]);
+}
+
+#[test]
+fn it_never_reuses_ids() {
+    let c = double_decker::Bus::<bool>::new();
+    assert_eq!(c.peek_next_id(), 0);
+
+    let _r1 = c.add_rx();
+    let _r2 = c.add_rx_with_capacity(1);
+    let (_r3, h3) = c.add_rx_with_handle();
+    assert_eq!(c.peek_next_id(), 3);
+
+    // Removing receivers doesn't free up their ids
+    c.remove_rx(h3);
+    c.clear();
+    assert_eq!(c.peek_next_id(), 3);
+
+    let (_r4, h4) = c.add_rx_with_handle();
+    assert_ne!(h3, h4);
+    assert_eq!(c.peek_next_id(), 4);
+}
+
+#[test]
+fn it_broadcasts_from_a_factory() {
+    let c = double_decker::Bus::new();
+    let receivers = (0..3).map(|_| c.add_rx()).collect::<Vec<_>>();
+
+    let mut next = 0;
+    let delivered = c.broadcast_fn(|| {
+        next += 1;
+        next
+    });
+    assert_eq!(delivered, 3);
+
+    // Receivers are called in the order they were added
+    for (i, rx) in receivers.iter().enumerate() {
+        assert_eq!(rx.try_recv(), Ok(i + 1));
+    }
+}
+
+#[test]
+fn it_orders_concurrent_producers() {
+    use std::thread;
+
+    for _ in 0..100 {
+        let c = double_decker::Bus::new();
+        let r1 = c.add_rx();
+        let r2 = c.add_rx();
+
+        let producers = (0..2)
+            .map(|p| {
+                let c = c.clone();
+                thread::spawn(move || {
+                    for i in 0..100 {
+                        c.broadcast_ordered((p, i));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        drop(c);
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let seen1 = r1.iter().collect::<Vec<_>>();
+        let seen2 = r2.iter().collect::<Vec<_>>();
+        assert_eq!(seen1.len(), 200);
+        assert_eq!(seen1, seen2);
+    }
+}
+
+#[test]
+fn it_extends() {
+    let mut c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    c.extend(0..100);
+    assert_eq!(
+        r1.try_iter().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn it_never_clones_for_a_single_receiver() {
+    let clones = Arc::new(AtomicUsize::new(0));
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    for _ in 0..10 {
+        assert_eq!(c.broadcast(CountsClones(clones.clone())), 1);
+    }
+
+    assert_eq!(r1.try_iter().count(), 10);
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn it_broadcasts_from_a_sender_handle() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let r2 = c.receiver().add_rx();
+
+    let tx = c.sender();
+    #[allow(clippy::redundant_clone)]
+    let tx2 = tx.clone();
+
+    assert_eq!(tx.broadcast(1), 2);
+    assert_eq!(tx2.broadcast(2), 2);
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(r2.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+    // A sender is just a pointer to the shared bus so cloning it copies no subscribers
+    assert_eq!(
+        std::mem::size_of::<double_decker::BusSender<u32>>(),
+        std::mem::size_of::<usize>()
+    );
+}
+
+#[test]
+fn it_reports_receivers_that_time_out() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let id = c.peek_next_id();
+    let r2 = c.add_rx_with_capacity(1);
+
+    c.broadcast(0);
+    assert_eq!(
+        c.broadcast_timeout(1, std::time::Duration::from_millis(10)),
+        vec![id]
+    );
+
+    // Slow receivers aren't pruned
+    assert_eq!(c.subscriber_count(), 2);
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+    assert_eq!(r2.try_iter().collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn it_subscribes_to_many_buses() {
+    let c1 = double_decker::Bus::new();
+    let c2 = double_decker::Bus::new();
+    let (tx, rx) = crossbeam::channel::unbounded();
+
+    let _sub =
+        double_decker::subscribe_many(&[&c1, &c2], Box::new(move |event| tx.send(event).unwrap()));
+
+    c1.broadcast(1);
+    c2.broadcast(2);
+    drop(c1);
+    c2.broadcast(3);
+
+    let timeout = std::time::Duration::from_millis(100);
+    let mut seen = (0..3)
+        .map(|_| rx.recv_timeout(timeout).unwrap())
+        .collect::<Vec<_>>();
+    seen.sort_unstable();
+    assert_eq!(seen, vec![1, 2, 3]);
+}
+
+#[test]
+fn it_counts_broadcasts() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+    let r3 = c.add_rx();
+    drop(r3);
+
+    for i in 0..10 {
+        c.broadcast(i);
+    }
+
+    let stats = c.stats();
+    assert_eq!(stats.broadcasts, 10);
+    assert_eq!(stats.delivered, 20);
+    assert_eq!(stats.pruned, 1);
+    assert_eq!(stats.to_string(), "broadcasts=10 delivered=20 pruned=1");
+    assert_eq!(r1.try_iter().count(), 10);
+    assert_eq!(r2.try_iter().count(), 10);
+}
+
+#[test]
+fn it_reports_queue_depths() {
+    let c = double_decker::Bus::new();
+    let r0 = c.add_rx();
+    let _r1 = c.add_rx_priority(1);
+    assert_eq!(c.queue_depths(), vec![(0, 0), (1, 0)]);
+
+    c.broadcast(1);
+    c.broadcast(2);
+    assert_eq!(c.queue_depths(), vec![(0, 2), (1, 2)]);
+
+    r0.recv().unwrap();
+    assert_eq!(c.queue_depths(), vec![(0, 1), (1, 2)]);
+}
+
+#[test]
+fn it_shares_events_without_cloning() {
+    let clones = Arc::new(AtomicUsize::new(0));
+    let c = double_decker::Bus::<Arc<CountsClones>>::new();
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+    let r3 = c.add_rx();
+
+    assert_eq!(c.broadcast_shared(CountsClones(clones.clone())), 3);
+
+    let e1 = r1.try_recv().unwrap();
+    let e2 = r2.try_recv().unwrap();
+    let e3 = r3.try_recv().unwrap();
+    assert!(Arc::ptr_eq(&e1, &e2) && Arc::ptr_eq(&e2, &e3));
+    assert_eq!(Arc::strong_count(&e1), 3);
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn it_drains_buffered_events() {
+    use double_decker::RecvExt;
+
+    let mut c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    c.extend(0..3);
+    assert_eq!(r1.drain(), vec![0, 1, 2]);
+    assert_eq!(r1.drain(), Vec::<i32>::new());
+
+    let mut buf = vec![-1];
+    c.extend(3..5);
+    r1.drain_into(&mut buf);
+    c.extend(5..6);
+    r1.drain_into(&mut buf);
+    assert_eq!(buf, vec![-1, 3, 4, 5]);
+}
+
+#[test]
+fn it_skips_buffered_events() {
+    use double_decker::RecvExt;
+
+    let mut c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    c.extend(0..5);
+    assert_eq!(r1.skip(2), 2);
+    assert_eq!(r1.recv(), Ok(2));
+    // Only the buffered events are skipped
+    assert_eq!(r1.skip(5), 2);
+    assert_eq!(r1.skip(1), 0);
+}
+
+#[test]
+fn it_connects_buses() {
+    let a = double_decker::Bus::new();
+    let b = double_decker::Bus::new();
+    let rb = b.add_rx();
+
+    let _bridge = a.connect_to(&b);
+    a.broadcast(1);
+    a.broadcast(2);
+
+    let timeout = std::time::Duration::from_millis(100);
+    assert_eq!(rb.recv_timeout(timeout), Ok(1));
+    assert_eq!(rb.recv_timeout(timeout), Ok(2));
+}
+
+#[test]
+#[should_panic(expected = "Can't connect a bus to itself")]
+fn it_refuses_to_connect_a_bus_to_itself() {
+    let a = double_decker::Bus::<i32>::new();
+    let _bridge = a.connect_to(&a.clone());
+}
+
+#[test]
+fn it_numbers_broadcasts() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let (tx, rx) = crossbeam::channel::unbounded();
+    let _sub = c.subscribe_seq(Box::new(move |seq, event| tx.send((seq, event)).unwrap()));
+
+    for (i, event) in ["a", "b", "c"].iter().enumerate() {
+        assert_eq!(c.broadcast_seq(*event), Some(i as u64));
+    }
+
+    let expected = vec![(0, "a"), (1, "b"), (2, "c")];
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), expected);
+
+    let timeout = std::time::Duration::from_millis(100);
+    let seen = (0..3)
+        .map(|_| rx.recv_timeout(timeout).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn it_does_not_number_broadcasts_on_a_closed_bus() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    assert_eq!(c.broadcast_seq(true), Some(0));
+    c.close();
+    assert_eq!(c.broadcast_seq(true), None);
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![(0, true)]);
+}
+
+#[test]
+fn it_splits() {
+    let (c, r1) = double_decker::Bus::new().split();
+
+    c.broadcast(1);
+    assert_eq!(r1.try_recv(), Ok(1));
+    assert_eq!(c.subscriber_count(), 1);
+}
+
+#[test]
+fn it_keeps_receivers_added_while_pruning() {
+    use std::{sync::atomic::AtomicBool, thread};
+
+    let c = double_decker::Bus::new();
+    let stop = Arc::new(AtomicBool::new(false));
+
+    // Keeps the bus pruning dropped receivers
+    let churn = {
+        let c = c.clone();
+        let stop = stop.clone();
+        thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                drop(c.add_rx());
+                c.broadcast(0);
+            }
+        })
+    };
+
+    let mut receivers = Vec::new();
+    for i in 1..1_000 {
+        let r = c.add_rx();
+        c.broadcast(i);
+        assert!(r.try_iter().any(|event| event == i));
+        receivers.push(r);
+    }
+
+    stop.store(true, Ordering::SeqCst);
+    churn.join().unwrap();
+
+    // Every receiver we kept is still subscribed
+    c.broadcast(-1);
+    for r in receivers {
+        assert_eq!(r.try_iter().last(), Some(-1));
+    }
+}
+
+#[test]
+fn it_lists_subscriber_ids() {
+    let c = double_decker::Bus::<i32>::new();
+    let _r1 = c.add_rx();
+    let (_r2, handle) = c.add_rx_with_handle();
+    let _r3 = c.add_rx();
+    assert_eq!(c.subscriber_ids(), vec![0, 1, 2]);
+
+    c.remove_rx(handle);
+    assert_eq!(c.subscriber_ids(), vec![0, 2]);
+}
+
+#[test]
+fn it_broadcasts_cheap_clones() {
+    #[derive(Clone)]
+    struct Shared(Arc<CountsClones>);
+    impl double_decker::CheapClone for Shared {}
+
+    let clones = Arc::new(AtomicUsize::new(0));
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+
+    assert_eq!(
+        c.broadcast_cheap(Shared(Arc::new(CountsClones(clones.clone())))),
+        2
+    );
+    assert!(Arc::ptr_eq(
+        &r1.try_recv().unwrap().0,
+        &r2.try_recv().unwrap().0
+    ));
+    assert_eq!(clones.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn it_rejects_broadcasts_once_closed() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    c.broadcast(1);
+    assert!(!c.is_closed());
+    c.close();
+    assert!(c.is_closed());
+
+    assert_eq!(c.broadcast(2), 0);
+    assert_eq!(c.broadcast_ordered(3), 0);
+    assert_eq!(
+        c.try_broadcast(4),
+        Err(double_decker::BroadcastError::Closed)
+    );
+
+    // Events broadcast before closing can still be drained
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn it_iterates_over_a_bus() {
+    use std::thread;
+
+    let c = double_decker::Bus::new();
+    let events = (&c).into_iter();
+
+    let tx = c.clone();
+    let j = thread::spawn(move || {
+        for i in 0..3 {
+            tx.broadcast(i);
+        }
+    });
+
+    let mut seen = Vec::new();
+    for event in events {
+        seen.push(event);
+        if seen.len() == 3 {
+            break;
+        }
+    }
+
+    j.join().unwrap();
+    assert_eq!(seen, vec![0, 1, 2]);
+}
+
+#[test]
+fn it_waits_for_a_reply() {
+    use double_decker::SubscribeToReader;
+
+    let c = double_decker::Bus::<(i32, crossbeam::channel::Sender<i32>)>::new();
+    let _echo = c.subscribe_on_thread(Box::new(|(request, reply)| {
+        let _ = reply.try_send(request * 2);
+    }));
+
+    let timeout = std::time::Duration::from_millis(100);
+    assert_eq!(c.request_reply(|reply| (21, reply), timeout), Ok(42));
+}
+
+#[test]
+fn it_times_out_without_a_reply() {
+    let c = double_decker::Bus::<(i32, crossbeam::channel::Sender<i32>)>::new();
+    let _r1 = c.add_rx();
+
+    let timeout = std::time::Duration::from_millis(10);
+    assert_eq!(
+        c.request_reply(|reply| (21, reply), timeout),
+        Err(crossbeam::channel::RecvTimeoutError::Timeout)
+    );
+}
+
+#[test]
+fn it_reports_lifecycle_events() {
+    use double_decker::LifecycleEvent;
+
+    let c = double_decker::Bus::new();
+    let lifecycle = c.lifecycle();
+
+    let r1 = c.add_rx();
+    let id = c.subscriber_ids()[0];
+    drop(r1);
+    assert_eq!(lifecycle.try_recv(), Ok(LifecycleEvent::Added(id)));
+    assert_eq!(lifecycle.try_recv(), Err(TryRecvError::Empty));
+
+    // Dropped receivers are only reported once a broadcast prunes them
+    c.broadcast(0);
+    assert_eq!(lifecycle.try_recv(), Ok(LifecycleEvent::Removed(id)));
+    assert_eq!(lifecycle.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn it_only_broadcasts_changes() {
+    let c = double_decker::Bus::new();
+    let r1 = c.add_rx();
+
+    let broadcast = [1, 1, 2, 2]
+        .iter()
+        .map(|event| c.broadcast_if_changed(*event))
+        .collect::<Vec<_>>();
+
+    assert_eq!(broadcast, vec![true, false, true, false]);
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn it_sends_to_higher_priorities_first() {
+    // Only clones are marked, so the last receiver sent to gets the unmarked original
+    #[derive(Debug, PartialEq)]
+    struct Marked(bool);
+    impl Clone for Marked {
+        fn clone(&self) -> Self {
+            Marked(true)
+        }
+    }
+
+    let c = double_decker::Bus::new();
+    let normal = c.add_rx();
+    let logger = c.add_rx_priority(10);
+
+    c.broadcast(Marked(false));
+    assert_eq!(logger.try_recv(), Ok(Marked(true)));
+    assert_eq!(normal.try_recv(), Ok(Marked(false)));
+    assert_eq!(c.subscriber_ids(), vec![0, 1]);
+}
+
+#[test]
+fn it_broadcasts_copies() {
+    let c = double_decker::Bus::new();
+    let receivers = (0..3).map(|_| c.add_rx()).collect::<Vec<_>>();
+
+    assert_eq!(c.broadcast_copy(42), 3);
+    for r in receivers {
+        assert_eq!(r.try_recv(), Ok(42));
+    }
+}
+
+#[test]
+fn it_polls() {
+    let c = double_decker::Bus::new();
+    let mut subscription = double_decker::PollingSubscription::new(&c);
+
+    let mut seen = Vec::new();
+    assert_eq!(subscription.poll(&mut |event| seen.push(event)), 0);
+
+    c.broadcast(1);
+    c.broadcast(2);
+    assert_eq!(subscription.poll(&mut |event| seen.push(event)), 2);
+    c.broadcast(3);
+    assert_eq!(subscription.poll(&mut |event| seen.push(event)), 1);
+
+    assert_eq!(seen, vec![1, 2, 3]);
+}
+
+#[test]
+fn it_merges_buses() {
+    let clicks = double_decker::Bus::<i32>::new();
+    let keys = double_decker::Bus::<i32>::new();
+
+    let input = clicks.merge_map(|i| format!("click {}", i));
+    let _keys = input.feed_from(&keys, |i| format!("key {}", i));
+    let rx = input.add_rx();
+
+    clicks.broadcast(1);
+    keys.broadcast(2);
+
+    let timeout = std::time::Duration::from_millis(100);
+    let mut seen = vec![
+        rx.recv_timeout(timeout).unwrap(),
+        rx.recv_timeout(timeout).unwrap(),
+    ];
+    // Events from different sources can arrive in either order
+    seen.sort();
+    assert_eq!(seen, vec!["click 1".to_string(), "key 2".to_string()]);
+}
+
+#[test]
+fn it_is_send_and_sync() {
+    fn assert_send_sync<X: Send + Sync>() {}
+
+    // `Cell` isn't `Sync` but buses of it still are as every receiver gets its own clone
+    type Event = std::cell::Cell<i32>;
+    assert_send_sync::<double_decker::Bus<Event>>();
+    assert_send_sync::<double_decker::BusSender<Event>>();
+    assert_send_sync::<double_decker::BusReceiver<Event>>();
+    assert_send_sync::<double_decker::PollingSubscription<Event>>();
+    assert_send_sync::<double_decker::Subscription>();
+    assert_send_sync::<double_decker::SubscriptionGroup>();
+}
+
+#[test]
+fn it_reports_full_receivers() {
+    let c = double_decker::Bus::new();
+    let full = c.add_rx_with_capacity(1);
+    let _r = c.add_rx();
+    let dropped = c.add_rx();
+    drop(dropped);
+
+    c.broadcast_lossy(1);
+    let report = c.broadcast_lossy(2);
+    assert_eq!(report.delivered, 1);
+    assert_eq!(report.dropped_full, vec![0]);
+    assert!(report.disconnected.is_empty());
+    assert_eq!(
+        report.to_string(),
+        "delivered=1 dropped_full=1 disconnected=0"
+    );
+
+    // Full receivers stay subscribed
+    assert_eq!(c.subscriber_ids(), vec![0, 1]);
+    assert_eq!(full.try_recv(), Ok(1));
+    assert_eq!(c.broadcast_lossy(3).dropped_full, Vec::<usize>::new());
+}
+
+#[test]
+fn it_calls_the_pre_broadcast_hook() {
+    let c = double_decker::Bus::<i32>::new();
+    let count = Arc::new(AtomicUsize::new(0));
+    let hook_count = count.clone();
+    c.set_pre_broadcast_hook(Box::new(move |_event| {
+        hook_count.fetch_add(1, Ordering::SeqCst);
+    }));
+
+    // Called even when nobody is listening
+    c.broadcast(1);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    let _r1 = c.add_rx();
+    let _r2 = c.add_rx();
+    c.broadcast(2);
+    c.broadcast(3);
+    assert_eq!(count.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn it_reconnects_receivers() {
+    let c = double_decker::Bus::new();
+    let mut rx = c.add_rx_reconnecting();
+
+    c.broadcast(1);
+    assert_eq!(rx.recv(), Ok(1));
+
+    // Clearing disconnects the receiver so it adds itself back
+    c.clear();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(c.subscriber_count(), 1);
+
+    c.broadcast(2);
+    assert_eq!(rx.recv(), Ok(2));
+
+    drop(c);
+    assert_eq!(rx.recv(), Err(RecvError));
+}
+
+#[test]
+fn it_waits_for_subscribers() {
+    let c = double_decker::Bus::<i32>::new();
+    let timeout = std::time::Duration::from_millis(50);
+    assert!(!c.wait_for_subscribers(1, timeout));
+
+    let bus = c.clone();
+    let j = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        bus.add_rx()
+    });
+
+    assert!(c.wait_for_subscribers(1, std::time::Duration::from_secs(5)));
+    let rx = j.join().unwrap();
+
+    c.broadcast(1);
+    assert_eq!(rx.recv(), Ok(1));
+}
+
+#[test]
+fn it_limits_buffered_events() {
+    let c = double_decker::Bus::with_global_limit(4);
+    let stalled = c.add_rx();
+    let fast = c.add_rx();
+
+    for i in 0..10 {
+        c.broadcast(i);
+        assert_eq!(fast.recv(), Ok(i));
+    }
+
+    // The stalled receiver stops being sent events once the limit is reached
+    assert_eq!(stalled.try_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+    // Once it catches up it receives events again
+    c.broadcast(10);
+    assert_eq!(stalled.try_recv(), Ok(10));
+    assert_eq!(fast.try_recv(), Ok(10));
+}
+
+#[test]
+fn it_limits_buffered_events_on_every_broadcast_path() {
+    let c = double_decker::Bus::with_global_limit(4);
+    let stalled = c.add_rx();
+    let fast = c.add_rx();
+
+    for i in 0..12 {
+        match i % 4 {
+            0 => c.broadcast_ref(&i),
+            1 => c.broadcast_blocking(i),
+            2 => c.broadcast_lossy(i).delivered,
+            _ => c.broadcast_copy(i),
+        };
+        assert_eq!(fast.recv(), Ok(i));
+    }
+
+    assert_eq!(stalled.try_iter().collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn it_skips_every_receiver_tied_for_the_most_buffered_events() {
+    let c = double_decker::Bus::with_global_limit(4);
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+
+    assert_eq!(c.broadcast(1), 2);
+    assert_eq!(c.broadcast(2), 2);
+
+    // Both receivers are the slowest so neither gets the event
+    assert_eq!(c.broadcast(3), 0);
+    assert_eq!(r1.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(r2.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn it_captures_undelivered_events() {
+    let c = double_decker::Bus::new();
+    let _r0 = c.add_rx();
+    let r1 = c.add_rx();
+    drop(r1);
+
+    assert_eq!(c.broadcast_capture("lost"), (1, vec![(1, "lost")]));
+
+    // The dropped receiver was removed
+    assert_eq!(c.broadcast_capture("kept"), (1, vec![]));
+}
+
+#[test]
+fn it_builds_buses() {
+    let hooked = Arc::new(AtomicUsize::new(0));
+    let hook_count = hooked.clone();
+
+    let c = double_decker::BusBuilder::new()
+        .capacity_hint(2)
+        .global_limit(2)
+        .pre_broadcast_hook(Box::new(move |_event| {
+            hook_count.fetch_add(1, Ordering::SeqCst);
+        }))
+        .build();
+    let stalled = c.add_rx();
+
+    for i in 0..5 {
+        c.broadcast(i);
+    }
+
+    assert_eq!(hooked.load(Ordering::SeqCst), 5);
+    assert_eq!(stalled.try_iter().collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[test]
+fn it_broadcasts_defaults() {
+    let c = double_decker::Bus::<Vec<i32>>::new();
+    let rx = c.add_rx();
+
+    assert_eq!(c.broadcast_default(), 1);
+    assert_eq!(rx.try_recv(), Ok(vec![]));
+}
+
+#[test]
+fn it_notifies() {
+    let c = double_decker::Bus::<()>::new();
+    let r1 = c.add_rx();
+    let r2 = c.add_rx();
+
+    assert_eq!(c.notify(), 2);
+    assert_eq!(c.broadcast_default(), 2);
+    assert_eq!(r1.try_iter().count(), 2);
+    assert_eq!(r2.try_iter().count(), 2);
+}
+
+#[test]
+fn it_visits_subscriber_ids() {
+    let c = double_decker::Bus::<i32>::new();
+    let _r0 = c.add_rx();
+    let _r1 = c.add_rx_priority(1);
+    let _r2 = c.add_rx();
+
+    let mut count = 0;
+    c.for_each_subscriber_id(|_| count += 1);
+    assert_eq!(count, c.subscriber_count());
+
+    // Ids are visited in delivery order
+    let mut ids = Vec::new();
+    c.for_each_subscriber_id(|id| ids.push(id));
+    assert_eq!(ids, vec![1, 0, 2]);
+}
+
+#[test]
+fn it_counts_producers() {
+    let c = double_decker::Bus::<i32>::new();
+    assert_eq!(c.producer_count(), 1);
+
+    let clones = (0..3).map(|_| c.clone()).collect::<Vec<_>>();
+    let sender = c.sender();
+    assert_eq!(c.producer_count(), 5);
+
+    drop(clones);
+    assert_eq!(c.producer_count(), 2);
+    drop(sender);
+    assert_eq!(c.producer_count(), 1);
+}
+
+#[test]
+fn it_broadcasts_in_total_order() {
+    let c = double_decker::Bus::with_total_order();
+    let receivers = (0..4).map(|_| c.add_rx()).collect::<Vec<_>>();
+
+    let producers = (0..4)
+        .map(|p| {
+            let c = c.clone();
+            std::thread::spawn(move || {
+                for i in 0..1_000 {
+                    c.broadcast((p, i));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    let sequences = receivers
+        .iter()
+        .map(|rx| rx.try_iter().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(sequences[0].len(), 4_000);
+    for sequence in &sequences[1..] {
+        assert_eq!(sequence, &sequences[0]);
+    }
+}
+
+#[test]
+fn it_calls_taps() {
+    let c = double_decker::Bus::<i32>::new();
+    let tapped = Arc::new(Mutex::new(Vec::new()));
+
+    for tap in 0..2 {
+        let tapped = tapped.clone();
+        c.add_tap(Box::new(move |event| {
+            tapped.lock().unwrap().push((tap, *event));
+        }));
+    }
+
+    let rx = c.add_rx();
+    c.broadcast(1);
+    c.broadcast(2);
+
+    assert_eq!(
+        *tapped.lock().unwrap(),
+        vec![(0, 1), (1, 1), (0, 2), (1, 2)]
+    );
+    assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+}
+
+#[test]
+fn it_compares_bus_identity() {
+    let a = double_decker::Bus::<i32>::new();
+    let b = a.clone();
+    let c = double_decker::Bus::<i32>::new();
+
+    assert!(a.same_bus(&b));
+    assert!(!a.same_bus(&c));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+}
+
+#[test]
+fn it_signals_external_subscriptions_on_drop() {
+    let (terminate_tx, terminate_rx) = crossbeam::channel::unbounded();
+    let subscription = double_decker::Subscription::new(terminate_tx);
+    let clone = subscription.clone();
+
+    drop(subscription);
+    assert_eq!(terminate_rx.try_recv(), Err(TryRecvError::Empty));
+
+    clone.flush();
+    drop(clone);
+    assert_eq!(terminate_rx.try_recv(), Ok(()));
+}
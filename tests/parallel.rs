@@ -0,0 +1,21 @@
+#![cfg(feature = "rayon")]
+
+#[test]
+fn it_broadcasts_in_parallel() {
+    let c = double_decker::Bus::new();
+    let mut receivers = (0..1_000).map(|_| c.add_rx()).collect::<Vec<_>>();
+
+    // Drop every other receiver
+    let mut i = 0;
+    receivers.retain(|_| {
+        i += 1;
+        i % 2 == 0
+    });
+
+    assert_eq!(c.broadcast_parallel(42), 500);
+    assert_eq!(c.subscriber_count(), 500);
+
+    for rx in receivers {
+        assert_eq!(rx.try_iter().collect::<Vec<_>>(), vec![42]);
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "testing")]
+
+use double_decker::TestBus;
+
+#[test]
+fn it_collects_fan_out() {
+    let mut bus = TestBus::new();
+    assert_eq!(bus.broadcast_collect(0), Vec::<i32>::new());
+
+    bus.add_rx();
+    bus.add_rx();
+    bus.add_rx();
+    assert_eq!(bus.broadcast_collect(1), vec![1, 1, 1]);
+}
+
+#[test]
+fn it_collects_events_from_the_underlying_bus() {
+    let mut bus = TestBus::new();
+    bus.add_rx();
+
+    // Code under test broadcasts through the plain bus
+    bus.bus().broadcast(1);
+    assert_eq!(bus.broadcast_collect(2), vec![1, 2]);
+}
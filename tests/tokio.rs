@@ -0,0 +1,77 @@
+#![cfg(feature = "async")]
+
+use std::time::Duration;
+
+#[test]
+fn it_subscribes_on_a_task() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let bus = double_decker::Bus::new();
+    let (tx, rx) = crossbeam::channel::unbounded();
+
+    let subscription = runtime.block_on(async {
+        bus.subscribe_on_task(Box::new(move |event| {
+            tx.send(event).unwrap();
+        }))
+    });
+
+    bus.broadcast(1);
+    bus.broadcast(2);
+    assert_eq!(rx.recv_timeout(Duration::from_millis(100)), Ok(1));
+    assert_eq!(rx.recv_timeout(Duration::from_millis(100)), Ok(2));
+
+    // Dropping the subscription ends the task
+    drop(subscription);
+    assert_eq!(
+        rx.recv_timeout(Duration::from_millis(100)),
+        Err(crossbeam::channel::RecvTimeoutError::Disconnected)
+    );
+}
+
+#[test]
+fn it_joins_a_task() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let bus = double_decker::Bus::<i32>::new();
+    let (tx, rx) = crossbeam::channel::unbounded();
+
+    let subscription = runtime.block_on(async {
+        bus.subscribe_on_task(Box::new(move |_event| {
+            std::thread::sleep(Duration::from_millis(50));
+            tx.send(()).unwrap();
+        }))
+    });
+
+    bus.broadcast(1);
+    std::thread::sleep(Duration::from_millis(10));
+
+    // Waits for the callback that's running to return
+    assert!(subscription.clone().join().is_ok());
+    assert!(!subscription.is_alive());
+    assert_eq!(rx.try_recv(), Ok(()));
+}
+
+#[test]
+fn it_reports_task_panics_from_join() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let bus = double_decker::Bus::<i32>::new();
+    let subscription = runtime
+        .block_on(async { bus.subscribe_on_task(Box::new(|_event| panic!("Callback panicked"))) });
+
+    bus.broadcast(1);
+
+    // Let the callback panic before `join()` terminates the task
+    let start = std::time::Instant::now();
+    while subscription.is_alive() {
+        assert!(start.elapsed() < Duration::from_secs(1));
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(subscription.join().is_err());
+}